@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// Puzzle answer, kept typed so it can be emitted in machine-readable formats
+/// without scraping text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Unsigned(n.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Unsigned(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Unsigned(n as u64)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Signed(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Signed(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::error::ParseError;
+use crate::solver::Solution;
+
+/// Regression threshold used when none is given, as a fraction of the
+/// baseline median.
+pub const DEFAULT_THRESHOLD: f64 = 0.2;
+
+const BASELINE_HEADER: &str = "day\tpart\tparse_ns\tsolve_ns";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarises the samples, or returns `None` if there are none.
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        Some(Stats {
+            min: samples[0],
+            median: percentile(samples, 0.5),
+            p95: percentile(samples, 0.95),
+        })
+    }
+}
+
+// nearest-rank percentile over sorted samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+/// Runs `solution` over `input` `iterations` times, timing the parse and
+/// solve phases separately.
+pub fn measure(solution: Solution, input: &[u8], iterations: usize) -> Result<Measurement, ParseError> {
+    let iterations = iterations.max(1);
+    let mut parse_samples = Vec::with_capacity(iterations);
+    let mut solve_samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let run = solution(&mut &input[..])?;
+        parse_samples.push(run.parse_time);
+        solve_samples.push(run.solve_time);
+    }
+    Ok(Measurement {
+        parse: Stats::from_samples(&mut parse_samples).expect("At least one iteration"),
+        solve: Stats::from_samples(&mut solve_samples).expect("At least one iteration"),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve => write!(f, "solve"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub phase: Phase,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+/// Median timings per (day, part), stored as a TSV file with nanosecond
+/// values.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    medians: BTreeMap<(u8, u8), (Duration, Duration)>,
+}
+
+impl Baseline {
+    pub fn parse(contents: &str) -> Result<Baseline, String> {
+        let mut medians = BTreeMap::new();
+        for (idx, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line == BASELINE_HEADER {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            let parsed = match fields[..] {
+                [day, part, parse_ns, solve_ns] => day.parse().ok().zip(part.parse().ok()).zip(
+                    parse_ns.parse().ok().zip(solve_ns.parse().ok()),
+                ),
+                _ => None,
+            };
+            let ((day, part), (parse_ns, solve_ns)) =
+                parsed.ok_or(format!("line {}: expected {}", idx + 1, BASELINE_HEADER))?;
+            medians.insert(
+                (day, part),
+                (Duration::from_nanos(parse_ns), Duration::from_nanos(solve_ns)),
+            );
+        }
+        Ok(Baseline { medians })
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, measurement: &Measurement) {
+        self.medians
+            .insert((day, part), (measurement.parse.median, measurement.solve.median));
+    }
+
+    /// Phases whose median is slower than the baseline by more than
+    /// `threshold` (a fraction, e.g. 0.2 for 20%).
+    pub fn regressions(&self, day: u8, part: u8, measurement: &Measurement, threshold: f64) -> Vec<Regression> {
+        let Some(&(parse, solve)) = self.medians.get(&(day, part)) else {
+            return Vec::new();
+        };
+        [
+            (Phase::Parse, parse, measurement.parse.median),
+            (Phase::Solve, solve, measurement.solve.median),
+        ]
+        .into_iter()
+        .filter(|(_, baseline, current)| current.as_secs_f64() > baseline.as_secs_f64() * (1.0 + threshold))
+        .map(|(phase, baseline, current)| Regression { phase, baseline, current })
+        .collect()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", BASELINE_HEADER)?;
+        for ((day, part), (parse, solve)) in self.medians.iter() {
+            writeln!(f, "{}\t{}\t{}\t{}", day, part, parse.as_nanos(), solve.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let mut samples = millis(&[20, 1, 5, 3, 4, 2, 7, 6, 9, 8]);
+        let expected = Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(5),
+            p95: Duration::from_millis(20),
+        };
+        assert_eq!(Stats::from_samples(&mut samples), Some(expected));
+        assert_eq!(Stats::from_samples(&mut []), None);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stats = |ms| Stats {
+            min: Duration::from_millis(ms),
+            median: Duration::from_millis(ms),
+            p95: Duration::from_millis(ms),
+        };
+        let mut baseline = Baseline::default();
+        baseline.insert(9, 2, &Measurement { parse: stats(3), solve: stats(1) });
+
+        assert_eq!(baseline.to_string(), "day\tpart\tparse_ns\tsolve_ns\n9\t2\t3000000\t1000000\n");
+        assert_eq!(Baseline::parse(&baseline.to_string()), Ok(baseline));
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = Baseline::parse("9\t2\t1000\t1000").unwrap();
+        let stats = |ns| Stats {
+            min: Duration::from_nanos(ns),
+            median: Duration::from_nanos(ns),
+            p95: Duration::from_nanos(ns),
+        };
+        let measurement = Measurement { parse: stats(1100), solve: stats(1500) };
+
+        let expected = vec![Regression {
+            phase: Phase::Solve,
+            baseline: Duration::from_nanos(1000),
+            current: Duration::from_nanos(1500),
+        }];
+        assert_eq!(baseline.regressions(9, 2, &measurement, 0.2), expected);
+        assert!(baseline.regressions(1, 1, &measurement, 0.2).is_empty());
+    }
+}
@@ -0,0 +1,524 @@
+use advent_2023::bench::{self, Baseline, Stats};
+use advent_2023::day_01::{self, decode::{self, Policy}, tokens::Matcher};
+use advent_2023::day_02::{self, analysis, p1::CubeSample};
+use advent_2023::inputs::Inputs;
+use advent_2023::output::{Format, Record};
+use advent_2023::registry;
+use advent_2023::solver::Solution;
+use advent_2023::verify::{self, Answers, Outcome};
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage:
+  advent run --day <day> --part <part> [--input <path>] [options]
+  advent run --all [options]
+  advent verify [--answers <path>] [options]
+  advent bench [--day <day>] [--part <part>] [--iterations <n>] [bench options] [options]
+  advent games [--bag <colors>] [--input <path>] [options]
+  advent calibrate [--part <part>] [--policy <policy>] [--input <path>] [options]
+  advent list
+
+Options:
+  --input-dir <dir>   directory holding the inputs (default: inputs, or $ADVENT_INPUT_DIR)
+  --cache-dir <dir>   offline cache for inputs read from stdin (default: $ADVENT_CACHE_DIR)
+  --format <format>   plain (default), json (one object per line) or tsv
+  --answers <path>    recorded answers to verify against (default: answers.toml)
+  --bag <colors>      day 2 bag as color=count pairs (default: red=12,green=13,blue=14)
+  --policy <policy>   day 1 lines without a digit: error (default), skip or zero
+
+Bench options:
+  --iterations <n>         runs per puzzle (default: 10)
+  --baseline <path>        flag medians slower than this baseline file
+  --threshold <percent>    slowdown tolerated before flagging (default: 20)
+  --write-baseline <path>  store the measured medians as a new baseline
+
+The input for day N, part P is read from day_NN_pP.txt or day_NN.txt, looked
+up in the input directory and then in the cache. Single puzzles without such a
+file read stdin, which can also be forced with --input -.";
+
+#[derive(Debug, PartialEq)]
+enum Input {
+    Discover,
+    Stdin,
+    File(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run { day: u8, part: u8, input: Input, inputs: Inputs, format: Format },
+    RunAll { inputs: Inputs, format: Format },
+    Verify { inputs: Inputs, answers: PathBuf },
+    Bench(BenchOptions),
+    Games { bag: CubeSample, input: Input, inputs: Inputs },
+    Calibrate { part: u8, policy: Policy, input: Input, inputs: Inputs },
+    List,
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchOptions {
+    day: Option<u8>,
+    part: Option<u8>,
+    iterations: usize,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    write_baseline: Option<PathBuf>,
+    inputs: Inputs,
+}
+
+const DEFAULT_ITERATIONS: usize = 10;
+
+fn parse_number<T>(flag: &str, value: Option<String>) -> Result<T, String> where T: std::str::FromStr {
+    let value = value.ok_or(format!("Missing value for {}", flag))?;
+    value.parse().map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_path(flag: &str, value: Option<String>) -> Result<PathBuf, String> {
+    value.map(PathBuf::from).ok_or(format!("Missing value for {}", flag))
+}
+
+fn parse_input(value: Option<String>) -> Result<Input, String> {
+    match parse_path("--input", value)? {
+        path if path.as_os_str() == "-" => Ok(Input::Stdin),
+        path => Ok(Input::File(path)),
+    }
+}
+
+fn parse_args<I>(mut args: I, mut inputs: Inputs) -> Result<Command, String> where I: Iterator<Item = String> {
+    match args.next().as_deref() {
+        Some("list") => Ok(Command::List),
+        Some("games") => {
+            let mut bag = day_02::p1::reference_bag();
+            let mut input = Input::Discover;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--bag" => bag = analysis::parse_bag(&args.next().ok_or("Missing value for --bag")?)?,
+                    "--input" => input = parse_input(args.next())?,
+                    "--input-dir" => inputs = inputs.with_dir(parse_path("--input-dir", args.next())?),
+                    "--cache-dir" => inputs = inputs.with_cache(parse_path("--cache-dir", args.next())?),
+                    _ => return Err(format!("Unknown argument: {}", arg)),
+                }
+            }
+            Ok(Command::Games { bag, input, inputs })
+        }
+        Some("calibrate") => {
+            let mut part = 1;
+            let mut policy = Policy::default();
+            let mut input = Input::Discover;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--part" => part = parse_number("--part", args.next())?,
+                    "--policy" => policy = args.next().ok_or("Missing value for --policy")?.parse()?,
+                    "--input" => input = parse_input(args.next())?,
+                    "--input-dir" => inputs = inputs.with_dir(parse_path("--input-dir", args.next())?),
+                    "--cache-dir" => inputs = inputs.with_cache(parse_path("--cache-dir", args.next())?),
+                    _ => return Err(format!("Unknown argument: {}", arg)),
+                }
+            }
+            if !(1..=2).contains(&part) {
+                return Err(format!("Invalid value for --part: {} (expected 1 or 2)", part));
+            }
+            Ok(Command::Calibrate { part, policy, input, inputs })
+        }
+        Some("verify") => {
+            let mut answers = PathBuf::from(verify::DEFAULT_PATH);
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--answers" => answers = parse_path("--answers", args.next())?,
+                    "--input-dir" => inputs = inputs.with_dir(parse_path("--input-dir", args.next())?),
+                    "--cache-dir" => inputs = inputs.with_cache(parse_path("--cache-dir", args.next())?),
+                    _ => return Err(format!("Unknown argument: {}", arg)),
+                }
+            }
+            Ok(Command::Verify { inputs, answers })
+        }
+        Some("bench") => {
+            let mut options = BenchOptions {
+                day: None,
+                part: None,
+                iterations: DEFAULT_ITERATIONS,
+                baseline: None,
+                threshold: bench::DEFAULT_THRESHOLD,
+                write_baseline: None,
+                inputs,
+            };
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--day" => options.day = Some(parse_number("--day", args.next())?),
+                    "--part" => options.part = Some(parse_number("--part", args.next())?),
+                    "--iterations" => options.iterations = parse_number("--iterations", args.next())?,
+                    "--baseline" => options.baseline = Some(parse_path("--baseline", args.next())?),
+                    "--threshold" => {
+                        options.threshold = parse_number::<f64>("--threshold", args.next())? / 100.0
+                    }
+                    "--write-baseline" => {
+                        options.write_baseline = Some(parse_path("--write-baseline", args.next())?)
+                    }
+                    "--input-dir" => {
+                        options.inputs = options.inputs.with_dir(parse_path("--input-dir", args.next())?)
+                    }
+                    "--cache-dir" => {
+                        options.inputs = options.inputs.with_cache(parse_path("--cache-dir", args.next())?)
+                    }
+                    _ => return Err(format!("Unknown argument: {}", arg)),
+                }
+            }
+            Ok(Command::Bench(options))
+        }
+        Some("run") => {
+            let mut day = None;
+            let mut part = None;
+            let mut input = Input::Discover;
+            let mut all = false;
+            let mut format = Format::Plain;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--day" => day = Some(parse_number("--day", args.next())?),
+                    "--part" => part = Some(parse_number("--part", args.next())?),
+                    "--input" => input = parse_input(args.next())?,
+                    "--input-dir" => inputs = inputs.with_dir(parse_path("--input-dir", args.next())?),
+                    "--cache-dir" => inputs = inputs.with_cache(parse_path("--cache-dir", args.next())?),
+                    "--format" => {
+                        format = args.next().ok_or("Missing value for --format")?.parse()?
+                    }
+                    "--all" => all = true,
+                    _ => return Err(format!("Unknown argument: {}", arg)),
+                }
+            }
+            if all {
+                Ok(Command::RunAll { inputs, format })
+            } else {
+                Ok(Command::Run {
+                    day: day.ok_or("Missing --day")?,
+                    part: part.ok_or("Missing --part")?,
+                    input,
+                    inputs,
+                    format,
+                })
+            }
+        }
+        Some(command) => Err(format!("Unknown command: {}", command)),
+        None => Err(String::from("Missing command")),
+    }
+}
+
+fn run(day: u8, part: u8, solution: Solution, mut reader: Box<dyn BufRead>) -> Result<Record, Box<dyn Error>> {
+    let run = solution(&mut reader)?;
+    Ok(Record { day, part, elapsed: run.elapsed(), answer: run.answer })
+}
+
+fn verify_all(
+    registry: &BTreeMap<(u8, u8), Solution>,
+    inputs: &Inputs,
+    answers: &Answers,
+) -> Vec<((u8, u8), Outcome)> {
+    registry
+        .iter()
+        .map(|(&(day, part), solution)| {
+            let Some(path) = inputs.find(day, part) else {
+                return ((day, part), Outcome::MissingInput);
+            };
+            let outcome = match File::open(path) {
+                Ok(file) => match solution(&mut BufReader::new(file)) {
+                    Ok(run) => answers.check(day, part, &run.answer),
+                    Err(error) => Outcome::Error(error.to_string()),
+                },
+                Err(error) => Outcome::Error(error.to_string()),
+            };
+            ((day, part), outcome)
+        })
+        .collect()
+}
+
+fn format_stats(stats: &Stats) -> String {
+    let millis = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+    format!(
+        "min {:.3} ms, median {:.3} ms, p95 {:.3} ms",
+        millis(stats.min),
+        millis(stats.median),
+        millis(stats.p95)
+    )
+}
+
+fn bench_all(registry: &BTreeMap<(u8, u8), Solution>, options: BenchOptions) -> Result<bool, Box<dyn Error>> {
+    let baseline = match &options.baseline {
+        Some(path) => Some(Baseline::parse(&fs::read_to_string(path)?)?),
+        None => None,
+    };
+    let mut measured = Baseline::default();
+    let mut regressed = false;
+    let selected = registry.iter().filter(|((day, part), _)| {
+        options.day.is_none_or(|d| d == *day) && options.part.is_none_or(|p| p == *part)
+    });
+    for (&(day, part), solution) in selected {
+        let Some(path) = options.inputs.find(day, part) else {
+            eprintln!("day {:02} part {}: no input in {}", day, part, options.inputs.dir().display());
+            continue;
+        };
+        let measurement = bench::measure(*solution, &fs::read(path)?, options.iterations)?;
+        println!("day {:02} part {}", day, part);
+        println!("  parse: {}", format_stats(&measurement.parse));
+        println!("  solve: {}", format_stats(&measurement.solve));
+        if let Some(baseline) = &baseline {
+            for regression in baseline.regressions(day, part, &measurement, options.threshold) {
+                regressed = true;
+                println!(
+                    "  REGRESSION in {}: median {:.3} ms (baseline {:.3} ms)",
+                    regression.phase,
+                    regression.current.as_secs_f64() * 1000.0,
+                    regression.baseline.as_secs_f64() * 1000.0
+                );
+            }
+        }
+        measured.insert(day, part, &measurement);
+    }
+    if let Some(path) = &options.write_baseline {
+        fs::write(path, measured.to_string())?;
+    }
+    Ok(regressed)
+}
+
+fn open(day: u8, part: u8, input: Input, inputs: &Inputs) -> io::Result<Box<dyn BufRead>> {
+    match input {
+        Input::Discover => inputs.open(day, part),
+        Input::Stdin => Ok(Box::new(io::stdin().lock())),
+        Input::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(env::args().skip(1), Inputs::from_env()) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let registry = registry();
+    match command {
+        Command::List => {
+            for (day, part) in registry.keys() {
+                println!("day {:02} part {}", day, part);
+            }
+        }
+        Command::Run { day, part, input, inputs, format } => {
+            let Some(solution) = registry.get(&(day, part)) else {
+                eprintln!("No solution registered for day {} part {}", day, part);
+                return ExitCode::FAILURE;
+            };
+            let result = open(day, part, input, &inputs)
+                .map_err(Box::from)
+                .and_then(|reader| run(day, part, *solution, reader));
+            match result {
+                Ok(record) => {
+                    if let Some(header) = format.header() {
+                        println!("{}", header);
+                    }
+                    println!("{}", format.render(&record));
+                }
+                Err(error) => {
+                    eprintln!("{}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::RunAll { inputs, format } => {
+            if let Some(header) = format.header() {
+                println!("{}", header);
+            }
+            let mut failed = false;
+            for ((day, part), solution) in registry.iter() {
+                let Some(path) = inputs.find(*day, *part) else {
+                    eprintln!("day {:02} part {}: no input in {}", day, part, inputs.dir().display());
+                    continue;
+                };
+                let result = File::open(path)
+                    .map_err(Box::from)
+                    .and_then(|file| run(*day, *part, *solution, Box::new(BufReader::new(file))));
+                match result {
+                    Ok(record) => println!("{}", format.render(&record)),
+                    Err(error) => {
+                        failed = true;
+                        eprintln!("day {:02} part {}: {}", day, part, error);
+                    }
+                }
+            }
+            // missing inputs are expected, failing solvers are not.
+            if failed {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Verify { inputs, answers } => {
+            let answers = match fs::read_to_string(&answers) {
+                Ok(contents) => Answers::parse(&contents)
+                    .map_err(|e| format!("{}: {}", answers.display(), e)),
+                Err(error) => Err(format!("{}: {}", answers.display(), error)),
+            };
+            let answers = match answers {
+                Ok(answers) => answers,
+                Err(message) => {
+                    eprintln!("{}", message);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let outcomes = verify_all(&registry, &inputs, &answers);
+            for ((day, part), outcome) in outcomes.iter() {
+                println!("day {:02} part {}: {}", day, part, outcome);
+            }
+            if outcomes.iter().any(|(_, outcome)| outcome.is_failure()) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Games { bag, input, inputs } => {
+            let games = open(day_02::DAY, 1, input, &inputs)
+                .map_err(Box::<dyn Error>::from)
+                .and_then(|reader| Ok(day_02::p1::parse_input(reader)?));
+            match games {
+                Ok(games) => print!("{}", analysis::Report { games: &games, bag: &bag }),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Calibrate { part, policy, input, inputs } => {
+            let (matcher, expected) = match part {
+                1 => (Matcher::digits(), "a digit"),
+                _ => (Matcher::english(), "a digit or spelled-out digit"),
+            };
+            let totals = open(day_01::DAY, part, input, &inputs)
+                .map_err(Box::<dyn Error>::from)
+                .and_then(|reader| Ok(decode::fold(reader, matcher, policy, expected)?));
+            match totals {
+                Ok(totals) => print!("{}", totals),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Bench(options) => match bench_all(&registry, options) {
+            Ok(false) => {}
+            Ok(true) => return ExitCode::FAILURE,
+            Err(error) => {
+                eprintln!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        },
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|a| a.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        let expected = Command::Run {
+            day: 9,
+            part: 2,
+            input: Input::File("in.txt".into()),
+            inputs: Inputs::default(),
+            format: Format::Plain,
+        };
+        let actual = parse_args(
+            args(&["run", "--day", "9", "--part", "2", "--input", "in.txt"]),
+            Inputs::default(),
+        );
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_run_from_stdin() {
+        let actual = parse_args(args(&["run", "--day", "1", "--part", "1", "--input", "-"]), Inputs::default());
+        assert!(matches!(actual, Ok(Command::Run { input: Input::Stdin, .. })));
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        let expected = Command::RunAll {
+            inputs: Inputs::new("puzzles").with_cache("cache"),
+            format: Format::Json,
+        };
+        let actual = parse_args(
+            args(&["run", "--all", "--input-dir", "puzzles", "--cache-dir", "cache", "--format", "json"]),
+            Inputs::default(),
+        );
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        let expected = Command::Verify {
+            inputs: Inputs::default(),
+            answers: "recorded.toml".into(),
+        };
+        let actual = parse_args(args(&["verify", "--answers", "recorded.toml"]), Inputs::default());
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        let expected = Command::Bench(BenchOptions {
+            day: Some(9),
+            part: None,
+            iterations: 50,
+            baseline: Some("baseline.tsv".into()),
+            threshold: 0.5,
+            write_baseline: None,
+            inputs: Inputs::default(),
+        });
+        let actual = parse_args(
+            args(&["bench", "--day", "9", "--iterations", "50", "--baseline", "baseline.tsv", "--threshold", "50"]),
+            Inputs::default(),
+        );
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_games() {
+        let expected = Command::Games {
+            bag: CubeSample::from([("red", 1), ("teal", 2)]),
+            input: Input::Stdin,
+            inputs: Inputs::default(),
+        };
+        let actual = parse_args(args(&["games", "--bag", "red=1,teal=2", "--input", "-"]), Inputs::default());
+        assert_eq!(actual, Ok(expected));
+        assert!(parse_args(args(&["games", "--bag", "red"]), Inputs::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_calibrate() {
+        let expected = Command::Calibrate {
+            part: 2,
+            policy: Policy::Skip,
+            input: Input::Discover,
+            inputs: Inputs::default(),
+        };
+        let actual = parse_args(args(&["calibrate", "--part", "2", "--policy", "skip"]), Inputs::default());
+        assert_eq!(actual, Ok(expected));
+        assert!(parse_args(args(&["calibrate", "--policy", "lenient"]), Inputs::default()).is_err());
+        assert!(parse_args(args(&["calibrate", "--part", "3"]), Inputs::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_with_unknown_format() {
+        assert!(parse_args(args(&["run", "--all", "--format", "xml"]), Inputs::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_without_part() {
+        assert!(parse_args(args(&["run", "--day", "9"]), Inputs::default()).is_err());
+    }
+}
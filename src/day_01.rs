@@ -1 +1,81 @@
-pub mod p1;
\ No newline at end of file
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
+pub mod decode;
+pub mod p1;
+pub mod p2;
+pub mod tokens;
+
+pub const DAY: u8 = 1;
+
+pub struct Day01;
+
+impl Solver for Day01 {
+    // part 2 also reads spelled-out digits, so lines are only decoded when
+    // solving. They are kept as bytes as they need not be valid UTF-8.
+    type Input = Vec<Vec<u8>>;
+    type Output = u64;
+
+    // holds the whole document, like every other day: `decode::fold`, behind
+    // `advent calibrate`, is the way to read one in bounded memory.
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead {
+        decode::byte_lines(reader).enumerate().map(|(idx, l)| {
+            let l = l?;
+            match p2::calibration_value(&l) {
+                Some(_) => Ok(l),
+                None => {
+                    let l = String::from_utf8_lossy(&l);
+                    Err(ParseError::syntax(DAY, idx, &l, &l, "a digit or spelled-out digit"))
+                }
+            }
+        }).collect()
+    }
+
+    // lines without an ASCII digit are skipped, as set by `p1::POLICY`.
+    fn part1(input: &Self::Input) -> Self::Output {
+        match p1::POLICY {
+            decode::Policy::Skip | decode::Policy::Zero => {
+                input.iter().filter_map(p1::calibration_value).map(u64::from).sum()
+            }
+            decode::Policy::Error => panic!("Part 1 to skip lines that parsing accepted"),
+        }
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(input.iter().filter_map(p2::calibration_value).map(u64::from).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver;
+    use crate::test_support::{example, example_tests};
+
+    use super::*;
+
+    // the example in the puzzle text was reflowed into a paragraph.
+    const EXAMPLE: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+
+    example_tests! {
+        solver: Day01,
+        calibration => {
+            input: EXAMPLE,
+            part1: 142,
+            part2: 142,
+        },
+        spelled_out => {
+            input: example(include_str!("day_01/p2.rs"), "For example:"),
+            part1: 209,
+            part2: 281,
+        },
+    }
+
+    #[test]
+    fn test_part1_matches_p1_solve() {
+        let input = example(include_str!("day_01/p2.rs"), "For example:");
+        let run = solver::part1::<Day01>(&mut input.as_bytes()).unwrap();
+        assert_eq!(Ok(run.answer), p1::solve(input.as_bytes()));
+    }
+}
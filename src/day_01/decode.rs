@@ -0,0 +1,248 @@
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+use super::tokens::Matcher;
+use super::DAY;
+
+/// What to do with lines that contain no token at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Fail with a syntax error on the first such line.
+    #[default]
+    Error,
+    /// Leave the line out of the values.
+    Skip,
+    /// Count the line with a calibration value of zero.
+    Zero,
+}
+
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Policy::Error),
+            "skip" => Ok(Policy::Skip),
+            "zero" => Ok(Policy::Zero),
+            _ => Err(format!("Unsupported policy: {} (expected error, skip or zero)", s)),
+        }
+    }
+}
+
+/// Number of line numbers kept as a sample by `LineSet`.
+pub const SAMPLE_LINES: usize = 10;
+
+/// Count of the lines that needed special handling, along with the (1-based)
+/// numbers of the first `SAMPLE_LINES` of them, so memory stays bounded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineSet {
+    pub count: usize,
+    pub sample: Vec<usize>,
+}
+
+impl LineSet {
+    fn add(&mut self, number: usize) {
+        self.count += 1;
+        if self.sample.len() < SAMPLE_LINES {
+            self.sample.push(number);
+        }
+    }
+}
+
+impl fmt::Display for LineSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sample: Vec<String> = self.sample.iter().map(|n| n.to_string()).collect();
+        write!(f, "{}", sample.join(", "))?;
+        if self.count > self.sample.len() {
+            write!(f, ", ... ({} in total)", self.count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Calibration values decoded from a document, with the lines that needed
+/// special handling.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub values: Vec<u32>,
+    /// Lines without any token, handled according to the policy.
+    pub missing: LineSet,
+    /// Lines that are not valid UTF-8. They are still decoded, as tokens are
+    /// matched byte by byte.
+    pub non_utf8: LineSet,
+}
+
+impl Report {
+    pub fn sum(&self) -> u64 {
+        self.values.iter().map(|v| u64::from(*v)).sum()
+    }
+}
+
+// strips the "\n" or "\r\n" terminator from a line.
+fn strip_terminator(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+/// Lines of raw bytes, without their "\n" or "\r\n" terminator.
+pub fn byte_lines<R>(reader: R) -> impl Iterator<Item = Result<Vec<u8>, ParseError>> where R: BufRead {
+    reader.split(b'\n').enumerate().map(|(idx, l)| {
+        let l = l.map_err(|e| ParseError::io(DAY, idx, e))?;
+        Ok(strip_terminator(&l).to_vec())
+    })
+}
+
+/// Running totals of a document decoded line by line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub sum: u64,
+    pub lines: usize,
+    /// Lines without any token, handled according to the policy.
+    pub missing: LineSet,
+    /// Lines that are not valid UTF-8.
+    pub non_utf8: LineSet,
+}
+
+impl fmt::Display for Totals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "sum: {}", self.sum)?;
+        writeln!(f, "lines: {}", self.lines)?;
+        writeln!(f, "without a digit: {}", self.missing)?;
+        writeln!(f, "not UTF-8: {}", self.non_utf8)
+    }
+}
+
+// Reads the document line by line into a single buffer, handing the value of
+// every line kept by `policy` to `value` along with its index and bytes.
+// `totals.sum` is left for `value` to maintain.
+fn scan<R, F>(
+    mut reader: R,
+    matcher: &Matcher,
+    policy: Policy,
+    expected: &'static str,
+    mut value: F,
+) -> Result<Totals, ParseError>
+where
+    R: BufRead,
+    F: FnMut(&mut Totals, usize, &[u8], u32) -> Result<(), ParseError>,
+{
+    let mut totals = Totals::default();
+    let mut buf = Vec::new();
+    loop {
+        let idx = totals.lines;
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf).map_err(|e| ParseError::io(DAY, idx, e))? == 0 {
+            return Ok(totals);
+        }
+        totals.lines += 1;
+        let line = strip_terminator(&buf);
+        if std::str::from_utf8(line).is_err() {
+            totals.non_utf8.add(idx + 1);
+        }
+        match (matcher.calibration_value(line), policy) {
+            (Some(v), _) => value(&mut totals, idx, line, v)?,
+            (None, Policy::Error) => {
+                let line = String::from_utf8_lossy(line);
+                return Err(ParseError::syntax(DAY, idx, &line, &line, expected));
+            }
+            (None, Policy::Skip) => totals.missing.add(idx + 1),
+            (None, Policy::Zero) => {
+                totals.missing.add(idx + 1);
+                value(&mut totals, idx, line, 0)?;
+            }
+        }
+    }
+}
+
+/// Decodes every line with `matcher`, handling lines without tokens according
+/// to `policy`. `expected` describes a token in the errors.
+pub fn decode<R>(reader: R, matcher: &Matcher, policy: Policy, expected: &'static str) -> Result<Report, ParseError>
+where
+    R: BufRead,
+{
+    let mut values = Vec::new();
+    let totals = scan(reader, matcher, policy, expected, |_, _, _, v| {
+        values.push(v);
+        Ok(())
+    })?;
+    Ok(Report { values, missing: totals.missing, non_utf8: totals.non_utf8 })
+}
+
+/// Same as `decode`, but folds the values into a checked sum as lines
+/// arrive, reusing a single buffer, so memory does not grow with the
+/// document.
+pub fn fold<R>(reader: R, matcher: &Matcher, policy: Policy, expected: &'static str) -> Result<Totals, ParseError>
+where
+    R: BufRead,
+{
+    scan(reader, matcher, policy, expected, |totals, idx, line, v| {
+        totals.sum = totals.sum.checked_add(u64::from(v)).ok_or_else(|| {
+            let line = String::from_utf8_lossy(line);
+            ParseError::syntax(DAY, idx, &line, &line, "a total that fits in 64 bits")
+        })?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSY: &[u8] = b"1abc2\r\nnothing here\n\xffseven\xfe8\n\ntreb7uchet";
+
+    #[test]
+    fn test_decode_policies() {
+        let matcher = Matcher::english();
+
+        let skipped = decode(MESSY, matcher, Policy::Skip, "a digit").unwrap();
+        assert_eq!(skipped.values, vec![12, 78, 77]);
+        assert_eq!(skipped.missing, LineSet { count: 2, sample: vec![2, 4] });
+        assert_eq!(skipped.non_utf8, LineSet { count: 1, sample: vec![3] });
+
+        let zeroed = decode(MESSY, matcher, Policy::Zero, "a digit").unwrap();
+        assert_eq!(zeroed.values, vec![12, 0, 78, 0, 77]);
+        assert_eq!(zeroed.sum(), 167);
+    }
+
+    #[test]
+    fn test_decode_error_policy() {
+        let error = decode(MESSY, Matcher::digits(), Policy::Error, "a digit").unwrap_err();
+        assert_eq!(error.line(), 2);
+    }
+
+    #[test]
+    fn test_policy_from_str() {
+        assert_eq!("zero".parse(), Ok(Policy::Zero));
+        assert!("lenient".parse::<Policy>().is_err());
+    }
+
+    #[test]
+    fn test_fold_matches_decode() {
+        for policy in [Policy::Skip, Policy::Zero] {
+            let report = decode(MESSY, Matcher::english(), policy, "a digit").unwrap();
+            let totals = fold(MESSY, Matcher::english(), policy, "a digit").unwrap();
+            assert_eq!(totals.sum, report.sum());
+            assert_eq!((totals.missing, totals.non_utf8), (report.missing, report.non_utf8));
+            assert_eq!(totals.lines, 5);
+        }
+        assert_eq!(fold(MESSY, Matcher::digits(), Policy::Error, "a digit").unwrap_err().line(), 2);
+    }
+
+    #[test]
+    fn test_totals_display() {
+        let totals = fold(MESSY, Matcher::english(), Policy::Skip, "a digit").unwrap();
+        assert_eq!(totals.to_string(), "sum: 167\nlines: 5\nwithout a digit: 2, 4\nnot UTF-8: 3\n");
+    }
+
+    #[test]
+    fn test_line_sample_is_capped() {
+        let blank = [b'\n'; 25];
+        let totals = fold(&blank[..], Matcher::digits(), Policy::Zero, "a digit").unwrap();
+        assert_eq!(totals.missing.count, 25);
+        assert_eq!(totals.missing.sample, (1..=SAMPLE_LINES).collect::<Vec<_>>());
+        assert_eq!(totals.missing.to_string(), "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, ... (25 in total)");
+    }
+}
@@ -1,6 +1,12 @@
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 
-/**
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+use super::decode::{decode, fold, Policy};
+use super::tokens::Matcher;
+
+/*
 --- Day 1: Trebuchet?! ---
 Something is wrong with global snow production, and you've been selected to take
 a look. The Elves have even given you a map; on it, they've used stars to mark
@@ -39,30 +45,23 @@ Consider your entire calibration document. What is the sum of all of the
 calibration values?
 */
 
-pub fn parse_input<R>(reader: R) -> Vec<u32> where R: BufRead {
-    reader.lines().map(|l| {
-        // NB: assumes unicode
-        let l = l.expect("Line to be valid");
-        let mut iter = l.chars();
-        let mut rev_iter = l.chars().rev();
-        let first = iter
-            .find(|c| c.is_ascii_digit())
-            .expect("A digit to exist")
-            .to_digit(10)
-            .expect("To be representable in base 10");
-        let last = rev_iter
-            .find(|c| c.is_ascii_digit())
-            .expect("A digit to exist")
-            .to_digit(10)
-            .expect("To be representable in base 10");
-        first * 10 + last
-    }).collect()
+/// First and last ASCII digits of the line as a two-digit number.
+pub fn calibration_value(line: impl AsRef<[u8]>) -> Option<u32> {
+    Matcher::digits().calibration_value(line)
+}
+
+/// Policy of part 1 for lines without an ASCII digit. They are skipped, as
+/// the same document also holds lines with only spelled-out digits.
+pub const POLICY: Policy = Policy::Skip;
+
+pub fn parse_input<R>(reader: R) -> Result<Vec<u32>, ParseError> where R: BufRead {
+    Ok(decode(reader, Matcher::digits(), POLICY, "a digit")?.values)
 }
 
-pub fn solve<R, W>(reader: R, mut writer: W) where R: BufRead, W: Write {
-    let solution: u32 = parse_input(reader).iter().sum();
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = fold(reader, Matcher::digits(), POLICY, "a digit")?.sum;
 
-    write!(&mut writer, "The sum of all calibration values is: {}", solution).unwrap();
+    Ok(solution.into())
 }
 
 
@@ -74,7 +73,15 @@ mod tests {
     fn test_parse_input() {
         let input = b"1abc2\n\
                                  pqr3stu8vwx";
-        assert_eq!(parse_input(&input[..]), vec![12, 38]);
+        assert_eq!(parse_input(&input[..]), Ok(vec![12, 38]));
+    }
+
+    #[test]
+    fn test_parse_input_without_digit() {
+        let input = b"1abc2\n\
+                     pqrstu\n\
+                     treb7uchet";
+        assert_eq!(parse_input(&input[..]), Ok(vec![12, 77]));
     }
 
     #[test]
@@ -83,10 +90,7 @@ mod tests {
                      pqr3stu8vwx\n\
                      a1b2c3d4e5f\n\
                      treb7uchet";
-        let mut output = Vec::new();
-
-        solve(&input[..], &mut output);
 
-        assert_eq!(String::from_utf8(output).unwrap(), "The sum of all calibration values is: 142");
+        assert_eq!(solve(&input[..]), Ok(Answer::Unsigned(142)));
     }
 }
\ No newline at end of file
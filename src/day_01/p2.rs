@@ -0,0 +1,74 @@
+/*
+--- Part Two ---
+Your calculation isn't quite right. It looks like some of the digits are
+actually spelled out with letters: one, two, three, four, five, six, seven,
+eight, and nine also count as valid "digits".
+
+Equipped with this new information, you now need to find the real first and last
+digit on each line. For example:
+
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+
+In this example, the calibration values are 29, 83, 13, 24, 42, 14, and 76.
+Adding these together produces 281.
+
+What is the sum of all of the calibration values?
+*/
+
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+use super::decode::{decode, fold, Policy};
+use super::tokens::Matcher;
+
+/// First and last digits of the line as a two-digit number. Both ends are
+/// scanned independently, so overlapping names like "twone" yield 2 from the
+/// front and 1 from the back.
+pub fn calibration_value(line: impl AsRef<[u8]>) -> Option<u32> {
+    Matcher::english().calibration_value(line)
+}
+
+pub fn parse_input<R>(reader: R) -> Result<Vec<u32>, ParseError> where R: BufRead {
+    Ok(decode(reader, Matcher::english(), Policy::Error, "a digit or spelled-out digit")?.values)
+}
+
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = fold(reader, Matcher::english(), Policy::Error, "a digit or spelled-out digit")?.sum;
+
+    Ok(solution.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_value_with_overlapping_names() {
+        assert_eq!(calibration_value("twone"), Some(21));
+        assert_eq!(calibration_value("eightwo"), Some(82));
+        assert_eq!(calibration_value("xtwone3four"), Some(24));
+        assert_eq!(calibration_value("7pqrstsixteen"), Some(76));
+        assert_eq!(calibration_value("zero"), None);
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = b"two1nine\n\
+                     eightwothree\n\
+                     abcone2threexyz\n\
+                     xtwone3four\n\
+                     4nineeightseven2\n\
+                     zoneight234\n\
+                     7pqrstsixteen";
+
+        assert_eq!(solve(&input[..]), Ok(Answer::Unsigned(281)));
+    }
+}
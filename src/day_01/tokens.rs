@@ -0,0 +1,250 @@
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+
+const DIGIT_NAMES: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// Tokens recognised as digits by the calibration decoder, with their values.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenTable {
+    entries: Vec<(String, u32)>,
+}
+
+impl TokenTable {
+    pub fn new() -> TokenTable {
+        TokenTable::default()
+    }
+
+    /// The ASCII digits 0 to 9.
+    pub fn digits() -> TokenTable {
+        let mut table = TokenTable::new();
+        for value in 0..10 {
+            table.set(value.to_string(), value);
+        }
+        table
+    }
+
+    /// The ASCII digits plus "one" to "nine".
+    pub fn english() -> TokenTable {
+        let mut table = TokenTable::digits();
+        for (value, name) in (1..).zip(DIGIT_NAMES) {
+            table.set(String::from(name), value);
+        }
+        table
+    }
+
+    /// Builds a table from `(token, value)` pairs, failing on the first pair
+    /// `insert` rejects.
+    pub fn from_tokens<I, S>(tokens: I) -> Result<TokenTable, String>
+    where
+        I: IntoIterator<Item = (S, u32)>,
+        S: Into<String>,
+    {
+        let mut table = TokenTable::new();
+        for (token, value) in tokens {
+            table.insert(token, value)?;
+        }
+        Ok(table)
+    }
+
+    /// Adds a token, replacing the value of an existing one. Empty tokens are
+    /// rejected since they would match everywhere, and so are values above 9
+    /// since tokens stand for the digits of a two-digit number.
+    pub fn insert(&mut self, token: impl Into<String>, value: u32) -> Result<(), String> {
+        let token = token.into();
+        if token.is_empty() {
+            return Err(String::from("Empty token"));
+        }
+        if value > 9 {
+            return Err(format!("Value of {:?} is not a digit: {}", token, value));
+        }
+        self.set(token, value);
+        Ok(())
+    }
+
+    fn set(&mut self, token: String, value: u32) {
+        match self.entries.iter_mut().find(|(t, _)| *t == token) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((token, value)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+
+#[derive(Debug, Clone, Copy)]
+struct Token {
+    len: usize,
+    value: u32,
+}
+
+// Aho-Corasick automaton with every transition resolved, so scanning is a
+// single table lookup per byte.
+#[derive(Debug, Clone)]
+struct Automaton {
+    transitions: Vec<[usize; 256]>,
+    // longest token ending in each state, i.e. the one starting earliest.
+    longest: Vec<Option<Token>>,
+    max_len: usize,
+}
+
+impl Automaton {
+    fn new(tokens: impl Iterator<Item = (Vec<u8>, u32)>) -> Automaton {
+        let mut transitions = vec![[0; 256]];
+        let mut longest = vec![None];
+        let mut max_len = 0;
+        // trie, with 0 standing for a missing edge (the root is never a child).
+        for (token, value) in tokens {
+            let mut state = 0;
+            for &b in &token {
+                if transitions[state][b as usize] == 0 {
+                    transitions.push([0; 256]);
+                    longest.push(None);
+                    transitions[state][b as usize] = transitions.len() - 1;
+                }
+                state = transitions[state][b as usize];
+            }
+            longest[state] = Some(Token { len: token.len(), value });
+            max_len = max_len.max(token.len());
+        }
+
+        // breadth first, so failure states are complete before their use.
+        let mut fail = vec![0; transitions.len()];
+        let mut queue: VecDeque<usize> = transitions[0].iter().copied().filter(|&s| s != 0).collect();
+        while let Some(state) = queue.pop_front() {
+            if longest[state].is_none() {
+                longest[state] = longest[fail[state]];
+            }
+            let fallback = transitions[fail[state]];
+            for (b, next) in transitions[state].iter_mut().enumerate() {
+                if *next == 0 {
+                    *next = fallback[b];
+                } else {
+                    fail[*next] = fallback[b];
+                    queue.push_back(*next);
+                }
+            }
+        }
+        Automaton { transitions, longest, max_len }
+    }
+
+    // token starting earliest in `bytes`, preferring the longest on ties.
+    fn leftmost(&self, bytes: impl Iterator<Item = u8>) -> Option<u32> {
+        let mut state = 0;
+        let mut best: Option<(usize, Token)> = None;
+        for (end, b) in bytes.enumerate() {
+            // tokens ending from here on start after `end + 1 - max_len`.
+            if best.is_some_and(|(start, _)| start + self.max_len <= end) {
+                break;
+            }
+            state = self.transitions[state][b as usize];
+            if let Some(token) = self.longest[state] {
+                let start = end + 1 - token.len;
+                if best.is_none_or(|(best_start, _)| start <= best_start) {
+                    best = Some((start, token));
+                }
+            }
+        }
+        best.map(|(_, token)| token.value)
+    }
+}
+
+/// Finds the first and last tokens of a table in a line, each with a single
+/// pass: forwards for the first token and backwards, over the reversed
+/// tokens, for the last. Tokens may overlap, so "twone" starts with "two" and
+/// ends with "one".
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Matcher {
+    pub fn new(table: &TokenTable) -> Matcher {
+        let tokens = || table.entries.iter().map(|(t, v)| (t.as_bytes().to_vec(), *v));
+        Matcher {
+            forward: Automaton::new(tokens()),
+            backward: Automaton::new(tokens().map(|(mut t, v)| {
+                t.reverse();
+                (t, v)
+            })),
+        }
+    }
+
+    /// Shared matcher for `TokenTable::digits`.
+    pub fn digits() -> &'static Matcher {
+        static DIGITS: OnceLock<Matcher> = OnceLock::new();
+        DIGITS.get_or_init(|| Matcher::new(&TokenTable::digits()))
+    }
+
+    /// Shared matcher for `TokenTable::english`.
+    pub fn english() -> &'static Matcher {
+        static ENGLISH: OnceLock<Matcher> = OnceLock::new();
+        ENGLISH.get_or_init(|| Matcher::new(&TokenTable::english()))
+    }
+
+    /// Value of the first token in `line`, which need not be valid UTF-8.
+    pub fn first(&self, line: impl AsRef<[u8]>) -> Option<u32> {
+        self.forward.leftmost(line.as_ref().iter().copied())
+    }
+
+    /// Value of the last token in `line`, which need not be valid UTF-8.
+    pub fn last(&self, line: impl AsRef<[u8]>) -> Option<u32> {
+        self.backward.leftmost(line.as_ref().iter().rev().copied())
+    }
+
+    /// First and last token values combined as a two-digit number.
+    pub fn calibration_value(&self, line: impl AsRef<[u8]>) -> Option<u32> {
+        let line = line.as_ref();
+        Some(self.first(line)? * 10 + self.last(line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_overlaps() {
+        let matcher = Matcher::english();
+        assert_eq!(matcher.calibration_value("twone"), Some(21));
+        assert_eq!(matcher.calibration_value("eightwo"), Some(82));
+        assert_eq!(matcher.calibration_value("0ab"), Some(0));
+        assert_eq!(matcher.calibration_value("abc"), None);
+    }
+
+    #[test]
+    fn test_custom_tokens() {
+        let table = TokenTable::from_tokens([("un", 1), ("deux", 2), ("trois", 3), ("quatre", 4)]).unwrap();
+        let matcher = Matcher::new(&table);
+        assert_eq!(matcher.calibration_value("xdeuxyztroisun"), Some(21));
+        assert_eq!(matcher.calibration_value("quatre"), Some(44));
+        assert_eq!(matcher.calibration_value("one 2"), None);
+    }
+
+    #[test]
+    fn test_invalid_tokens() {
+        assert_eq!(TokenTable::from_tokens([("neuf", 9), ("dix", 10)]), Err(String::from("Value of \"dix\" is not a digit: 10")));
+        let mut table = TokenTable::new();
+        assert!(table.insert("", 1).is_err());
+        assert!(table.insert("big", u32::MAX).is_err());
+        assert!(table.is_empty());
+        assert_eq!(table.insert("neuf", 9), Ok(()));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn test_leftmost_longest() {
+        // "bc" ends first, but "abcd" starts earlier.
+        let matcher = Matcher::new(&TokenTable::from_tokens([("abcd", 1), ("bc", 2), ("cd", 3)]).unwrap());
+        assert_eq!(matcher.first("xabcd"), Some(1));
+        assert_eq!(matcher.last("xabcd"), Some(1));
+        assert_eq!(matcher.last("xabcdcd"), Some(3));
+    }
+}
@@ -1,2 +1,49 @@
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
+pub mod analysis;
+pub mod inference;
 pub mod p1;
-pub mod p2;
\ No newline at end of file
+pub mod p2;
+
+pub const DAY: u8 = 2;
+
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<p1::Game>;
+    type Output = u64;
+
+    // powers are checked here, as the parts cannot fail.
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead {
+        let games = p1::parse_input(reader)?;
+        p2::sum_powers(&games)?;
+        Ok(games)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        u64::from(p1::sum_possible_ids(input, &p1::reference_bag()))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(p2::sum_powers(input).expect("Parsed games to have powers that fit in 64 bits"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{example, example_tests};
+
+    use super::*;
+
+    example_tests! {
+        solver: Day02,
+        games => {
+            input: example(include_str!("day_02/p2.rs"), "example games from earlier"),
+            part1: 8,
+            part2: 2286,
+        },
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::p1::{CubeSample, Game};
+use super::p2::minimum_set;
+
+/// Parses a bag written as `color=count` pairs separated by commas, such as
+/// "red=12,green=13,blue=14".
+pub fn parse_bag(text: &str) -> Result<CubeSample, String> {
+    text.split(',')
+        .map(|pair| {
+            let (color, count) = pair
+                .split_once('=')
+                .ok_or(format!("Expected color=count, found {:?}", pair))?;
+            let count = count
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid count for {}: {:?}", color.trim(), count))?;
+            Ok((color.trim(), count))
+        })
+        .collect()
+}
+
+pub fn is_feasible(game: &Game, bag: &CubeSample) -> bool {
+    game.samples.iter().all(|s| s.fits_in(bag))
+}
+
+/// Games that could have been played with `bag`.
+pub fn feasible<'a>(games: &'a [Game], bag: &CubeSample) -> Vec<&'a Game> {
+    games.iter().filter(|g| is_feasible(g, bag)).collect()
+}
+
+/// The color that rules out a game: the one exceeding the bag by the most
+/// cubes, the alphabetically first on ties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    pub id: u32,
+    pub color: String,
+    pub needed: u32,
+    pub available: u32,
+}
+
+/// The binding color of `game`, or `None` if the game is feasible.
+pub fn binding_color(game: &Game, bag: &CubeSample) -> Option<Binding> {
+    let needed = minimum_set(game);
+    let excess = |count: u32, color: &str| count - bag.get(color);
+    needed
+        .iter()
+        .filter(|(color, count)| *count > bag.get(color))
+        // colors come in order, so only a strictly larger excess wins.
+        .reduce(|best, next| if excess(next.1, next.0) > excess(best.1, best.0) { next } else { best })
+        .map(|(color, count)| Binding {
+            id: game.id,
+            color: String::from(color),
+            needed: count,
+            available: bag.get(color),
+        })
+}
+
+/// The binding color of every game that is not feasible with `bag`.
+pub fn infeasible(games: &[Game], bag: &CubeSample) -> Vec<Binding> {
+    games.iter().filter_map(|g| binding_color(g, bag)).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorStats {
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+}
+
+/// Count statistics per color over every sample of every game. Samples
+/// without a color count as zero cubes of it.
+pub fn color_stats(games: &[Game]) -> BTreeMap<String, ColorStats> {
+    let samples: Vec<&CubeSample> = games.iter().flat_map(|g| g.samples.iter()).collect();
+    let mut stats = BTreeMap::new();
+    for color in samples.iter().flat_map(|s| s.colors()) {
+        if stats.contains_key(color) {
+            continue;
+        }
+        let counts = samples.iter().map(|s| s.get(color));
+        stats.insert(String::from(color), ColorStats {
+            min: counts.clone().min().unwrap_or(0),
+            max: counts.clone().max().unwrap_or(0),
+            mean: f64::from(counts.sum::<u32>()) / samples.len() as f64,
+        });
+    }
+    stats
+}
+
+/// Per-game feasibility table followed by the color statistics.
+pub struct Report<'a> {
+    pub games: &'a [Game],
+    pub bag: &'a CubeSample,
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<6} {:<9} {:<10} {:>6} {:>6}", "game", "feasible", "binding", "needed", "bag")?;
+        for game in self.games {
+            match binding_color(game, self.bag) {
+                None => writeln!(f, "{:<6} {:<9} {:<10} {:>6} {:>6}", game.id, "yes", "-", "-", "-")?,
+                Some(b) => writeln!(f, "{:<6} {:<9} {:<10} {:>6} {:>6}", game.id, "no", b.color, b.needed, b.available)?,
+            }
+        }
+        writeln!(f)?;
+        writeln!(f, "{:<10} {:>6} {:>6} {:>8}", "color", "min", "max", "mean")?;
+        for (color, stats) in color_stats(self.games) {
+            writeln!(f, "{:<10} {:>6} {:>6} {:>8.2}", color, stats.min, stats.max, stats.mean)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::example;
+
+    use super::super::p1::{parse_input, reference_bag};
+    use super::*;
+
+    fn games() -> Vec<Game> {
+        let input = example(include_str!("p2.rs"), "example games from earlier");
+        parse_input(input.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_feasible_and_binding() {
+        let games = games();
+        let bag = reference_bag();
+        assert_eq!(feasible(&games, &bag).iter().map(|g| g.id).collect::<Vec<_>>(), vec![1, 2, 5]);
+
+        let binding = infeasible(&games, &bag);
+        assert_eq!(binding.len(), 2);
+        assert_eq!(binding[0], Binding { id: 3, color: String::from("red"), needed: 20, available: 12 });
+        assert_eq!((binding[1].id, binding[1].color.as_str()), (4, "red"));
+    }
+
+    #[test]
+    fn test_binding_ties_pick_first_color() {
+        let games = parse_input(&b"Game 7: 2 red, 2 blue"[..]).unwrap();
+        let bag = CubeSample::from([("red", 1), ("blue", 1)]);
+        assert_eq!(binding_color(&games[0], &bag).map(|b| b.color), Some(String::from("blue")));
+    }
+
+    #[test]
+    fn test_color_stats() {
+        let stats = color_stats(&games());
+        assert_eq!(stats.keys().collect::<Vec<_>>(), vec!["blue", "green", "red"]);
+        assert_eq!((stats["red"].min, stats["red"].max), (0, 20));
+        assert!((stats["blue"].mean - 50.0 / 14.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_bag() {
+        assert_eq!(parse_bag("red=12, green=13,blue=14"), Ok(reference_bag()));
+        assert!(parse_bag("red:12").is_err());
+        assert!(parse_bag("red=many").is_err());
+    }
+}
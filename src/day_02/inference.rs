@@ -0,0 +1,168 @@
+use std::cmp::Ordering;
+
+use super::p1::{CubeSample, Game};
+use super::p2::minimum_set;
+
+// ln(n choose k), summed term by term as the counts involved are small.
+fn ln_choose(n: u32, k: u32) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    (0..k).map(|i| f64::from(n - i).ln() - f64::from(i + 1).ln()).sum()
+}
+
+/// Log-likelihood of drawing `sample` as one handful, without replacement,
+/// from `bag`. Colors missing from the bag hold no cubes.
+pub fn sample_log_likelihood(sample: &CubeSample, bag: &CubeSample) -> f64 {
+    // the terms below can both be -inf here, and their difference NaN.
+    if !sample.fits_in(bag) {
+        return f64::NEG_INFINITY;
+    }
+    let total: u32 = bag.iter().map(|(_, count)| count).sum();
+    let drawn: u32 = sample.iter().map(|(_, count)| count).sum();
+    let ways: f64 = sample.iter().map(|(color, count)| ln_choose(bag.get(color), count)).sum();
+    ways - ln_choose(total, drawn)
+}
+
+/// Log-likelihood of every handful of `game` coming from `bag`. Cubes are put
+/// back after each handful, so handfuls are independent. Impossible bags
+/// score negative infinity.
+pub fn log_likelihood(game: &Game, bag: &CubeSample) -> f64 {
+    game.samples.iter().map(|s| sample_log_likelihood(s, bag)).sum()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scored {
+    pub bag: CubeSample,
+    pub log_likelihood: f64,
+}
+
+fn by_likelihood(a: &Scored, b: &Scored) -> Ordering {
+    b.log_likelihood.total_cmp(&a.log_likelihood)
+}
+
+/// Scores every candidate bag for `game`, most likely first.
+pub fn rank(game: &Game, candidates: &[CubeSample]) -> Vec<Scored> {
+    let mut scored: Vec<Scored> = candidates
+        .iter()
+        .map(|bag| Scored { bag: bag.clone(), log_likelihood: log_likelihood(game, bag) })
+        .collect();
+    scored.sort_by(by_likelihood);
+    scored
+}
+
+/// Every bag over the colors seen in `game` that could have produced it and
+/// holds at most `max_total` cubes, enumerated lazily in increasing order of
+/// the counts of each color. There are a lot of them with many colors.
+pub fn candidates(game: &Game, max_total: u32) -> Candidates {
+    let minimum: Vec<(String, u32)> = minimum_set(game).iter().map(|(c, n)| (String::from(c), n)).collect();
+    let total = minimum.iter().map(|(_, n)| n).sum::<u32>();
+    let counts = (total <= max_total).then(|| minimum.iter().map(|(_, n)| *n).collect());
+    Candidates { minimum, max_total, counts }
+}
+
+pub struct Candidates {
+    minimum: Vec<(String, u32)>,
+    max_total: u32,
+    // next bag to yield, `None` once they are all out.
+    counts: Option<Vec<u32>>,
+}
+
+impl Iterator for Candidates {
+    type Item = CubeSample;
+
+    fn next(&mut self) -> Option<CubeSample> {
+        let counts = self.counts.as_mut()?;
+        let bag = self.minimum.iter().zip(counts.iter()).map(|((color, _), count)| (color.as_str(), *count)).collect();
+        // like an odometer: bump the last count that can grow once the ones
+        // after it go back to their minimum.
+        let mut total: u32 = counts.iter().sum();
+        let mut advanced = false;
+        for (count, (_, min)) in counts.iter_mut().zip(self.minimum.iter()).rev() {
+            if total < self.max_total {
+                *count += 1;
+                advanced = true;
+                break;
+            }
+            total -= *count - min;
+            *count = *min;
+        }
+        if !advanced {
+            self.counts = None;
+        }
+        Some(bag)
+    }
+}
+
+/// The most likely bag of at most `max_total` cubes, or `None` if even the
+/// minimum set does not fit. Ties go to the smaller bag. Candidates are
+/// scored as they are enumerated, so memory stays constant.
+pub fn maximum_likelihood(game: &Game, max_total: u32) -> Option<Scored> {
+    let total = |bag: &CubeSample| bag.iter().map(|(_, count)| count).sum::<u32>();
+    candidates(game, max_total)
+        .map(|bag| Scored { log_likelihood: log_likelihood(game, &bag), bag })
+        .reduce(|best, next| match next.log_likelihood.total_cmp(&best.log_likelihood) {
+            Ordering::Greater => next,
+            Ordering::Equal if total(&next.bag) < total(&best.bag) => next,
+            _ => best,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::p1::parse_input;
+    use super::*;
+
+    fn game(text: &str) -> Game {
+        parse_input(text.as_bytes()).unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_sample_log_likelihood() {
+        // 2 red out of 2 red and 2 blue: 1 way in 6.
+        let bag = CubeSample::from([("red", 2), ("blue", 2)]);
+        let sample = CubeSample::from([("red", 2)]);
+        assert!((sample_log_likelihood(&sample, &bag) - (1.0_f64 / 6.0).ln()).abs() < 1e-12);
+        assert_eq!(sample_log_likelihood(&CubeSample::from([("red", 3)]), &bag), f64::NEG_INFINITY);
+        assert_eq!(sample_log_likelihood(&CubeSample::from([("red", 5)]), &bag), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_rank() {
+        let game = game("Game 1: 3 red; 3 red; 1 blue");
+        let ranked = rank(&game, &[
+            CubeSample::from([("red", 2), ("blue", 5)]),
+            CubeSample::from([("red", 5), ("blue", 5)]),
+            CubeSample::from([("red", 9), ("blue", 1)]),
+        ]);
+        assert_eq!(ranked[0].bag, CubeSample::from([("red", 9), ("blue", 1)]));
+        assert_eq!(ranked[2].log_likelihood, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_maximum_likelihood() {
+        // whole-bag handfuls are only certain if the bag holds nothing else.
+        let game = game("Game 2: 2 red, 1 blue; 2 red, 1 blue");
+        let best = maximum_likelihood(&game, 10).unwrap();
+        assert_eq!(best.bag, CubeSample::from([("red", 2), ("blue", 1)]));
+        assert_eq!(best.log_likelihood, 0.0);
+        assert_eq!(candidates(&game, 4).count(), 3);
+        assert_eq!(maximum_likelihood(&game, 2), None);
+    }
+
+    #[test]
+    fn test_candidates_order() {
+        let game = game("Game 3: 1 red, 1 blue");
+        let bags: Vec<CubeSample> = candidates(&game, 4).collect();
+        assert_eq!(bags, vec![
+            CubeSample::from([("blue", 1), ("red", 1)]),
+            CubeSample::from([("blue", 1), ("red", 2)]),
+            CubeSample::from([("blue", 1), ("red", 3)]),
+            CubeSample::from([("blue", 2), ("red", 1)]),
+            CubeSample::from([("blue", 2), ("red", 2)]),
+            CubeSample::from([("blue", 3), ("red", 1)]),
+        ]);
+        assert_eq!(candidates(&game, 1).count(), 0);
+    }
+}
@@ -1,4 +1,12 @@
-use std::io::{BufRead, Write};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::parse::Line;
+
+use super::DAY;
 
 /*
 --- Day 2: Cube Conundrum ---
@@ -52,90 +60,141 @@ of those games?
 */
 
 
-#[derive(Debug, Default, PartialEq)]
+/// Cubes of each color, by color name. Colors without cubes are not stored,
+/// so samples compare equal regardless of how they were built.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CubeSample {
-    pub blue: u32,
-    pub green: u32,
-    pub red: u32,
+    counts: BTreeMap<String, u32>,
 }
 
-#[derive(Debug)]
+impl CubeSample {
+    pub fn new() -> CubeSample {
+        CubeSample::default()
+    }
+
+    /// Number of cubes of `color`, zero for colors never seen.
+    pub fn get(&self, color: &str) -> u32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, color: &str, count: u32) {
+        if count == 0 {
+            self.counts.remove(color);
+        } else {
+            self.counts.insert(String::from(color), count);
+        }
+    }
+
+    /// Colors with at least one cube, in alphabetical order.
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.counts.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts.iter().map(|(color, count)| (color.as_str(), *count))
+    }
+
+    /// Whether every color of `self` fits within `bag`.
+    pub fn fits_in(&self, bag: &CubeSample) -> bool {
+        self.iter().all(|(color, count)| count <= bag.get(color))
+    }
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for CubeSample {
+    fn from_iter<I>(iter: I) -> CubeSample where I: IntoIterator<Item = (&'a str, u32)> {
+        let mut sample = CubeSample::new();
+        for (color, count) in iter {
+            sample.set(color, count);
+        }
+        sample
+    }
+}
+
+impl<'a, const N: usize> From<[(&'a str, u32); N]> for CubeSample {
+    fn from(counts: [(&'a str, u32); N]) -> CubeSample {
+        counts.into_iter().collect()
+    }
+}
+
+/// Canonical form, e.g. "3 blue, 4 red", with colors in alphabetical order.
+impl fmt::Display for CubeSample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (color, count)) in self.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, color)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Game {
     pub id: u32,
     pub samples: Vec<CubeSample>,
 }
 
-fn parse_sample(sample: &str) -> CubeSample {
-    let mut cube_sample: CubeSample = Default::default();
-    for cube in sample.split(',') {
-        let cube = cube.trim();
-        let mut cube_split = cube.split(' ');
-        let quantity: u32 = cube_split
-            .next()
-            .expect("The sample quantity to be present")
-            .trim()
-            .parse()
-            .expect("Sample quantity to be parseable");
-        let color = cube_split.next().expect("The cube color to be present");
-        match color {
-            "blue" => cube_sample.blue = quantity,
-            "green" => cube_sample.green = quantity,
-            "red" => cube_sample.red = quantity,
-            _ => panic!("Unsupported color")
+/// Canonical form of a game record, which parses back into the same game as
+/// long as it has samples and none of them are empty.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (idx, sample) in self.samples.iter().enumerate() {
+            write!(f, "{} {}", if idx > 0 { ";" } else { "" }, sample)?;
         }
+        Ok(())
     }
-    cube_sample
 }
 
-fn parse_samples(samples: &str) -> Vec<CubeSample> {
-    samples
-        .split(';')
-        .map(parse_sample)
-        .collect()
+fn parse_sample(line: &Line, sample: &str) -> Result<CubeSample, ParseError> {
+    let mut cube_sample = CubeSample::new();
+    for cube in sample.split(',') {
+        let (quantity, color) = line.split_once(cube.trim(), ' ', "a cube quantity and color")?;
+        let quantity: u32 = line.number(quantity, "a cube quantity")?;
+        if color.is_empty() || !color.chars().all(char::is_alphabetic) {
+            return Err(line.error(color, "a color name"));
+        }
+        cube_sample.set(color, quantity);
+    }
+    Ok(cube_sample)
 }
 
-fn parse_game(game: String) -> Game {
-    let mut split = game.split(':');
-    let game_header = split.next().expect("Game header to be present.");
-    let samples = split.next().expect("Cube samples to be present").trim();
-
-    let id: u32 = game_header
-        .split(' ')
-        .nth(1)
-        .expect("Game ID to be present")
-        .parse()
-        .expect("Game ID to be parseable");
-    let samples = parse_samples(samples);
+fn parse_game(line: &Line) -> Result<Game, ParseError> {
+    let (id, samples) = line.header("Game")?;
+    let samples = samples
+        .split(';')
+        .map(|s| parse_sample(line, s))
+        .collect::<Result<_, _>>()?;
 
-    Game {id, samples}
+    Ok(Game {id, samples})
 }
 
-pub fn parse_input<R>(reader: R) -> Vec<Game> where R: BufRead {
-    reader.lines().map(|l| {
-        let l = l.expect("Line to be present");
-        parse_game(l)
+pub fn parse_input<R>(reader: R) -> Result<Vec<Game>, ParseError> where R: BufRead {
+    reader.lines().enumerate().map(|(idx, l)| {
+        let l = l.map_err(|e| ParseError::io(DAY, idx, e))?;
+        parse_game(&Line::new(DAY, idx, &l))
     }).collect()
 }
 
-pub fn solve<R, W>(reader: R, mut writer: W) where R: BufRead, W: Write {
-    let reference_sample = CubeSample {
-        green: 13,
-        blue: 14,
-        red: 12,
-    };
+/// The bag the Elf asks about in the puzzle: 12 red, 13 green and 14 blue
+/// cubes.
+pub fn reference_bag() -> CubeSample {
+    CubeSample::from([("red", 12), ("green", 13), ("blue", 14)])
+}
 
-    let solution: u32 = parse_input(reader)
+pub fn sum_possible_ids(games: &[Game], bag: &CubeSample) -> u32 {
+    games
         .iter()
-        .filter(|g| {
-            g.samples.iter().all(|s| {
-                s.blue <= reference_sample.blue &&
-                s.green <= reference_sample.green &&
-                s.red <= reference_sample.red
-            })
-        }).map(|g| g.id)
-        .sum();
+        .filter(|g| g.samples.iter().all(|s| s.fits_in(bag)))
+        .map(|g| g.id)
+        .sum()
+}
 
-    write!(&mut writer, "The sum of all possible game IDs is: {}", solution).unwrap();
+pub fn solve<R>(reader: R, bag: &CubeSample) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = sum_possible_ids(&parse_input(reader)?, bag);
+
+    Ok(solution.into())
 }
 
 #[cfg(test)]
@@ -146,10 +205,10 @@ mod tests {
     fn test_parse_input() {
         let input = b"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
 
-        let expected_sample = CubeSample { blue: 3, green: 0, red: 4};
+        let expected_sample = CubeSample::from([("blue", 3), ("green", 0), ("red", 4)]);
         let expected_game_id = 1;
 
-        let actual_games = parse_input(&input[..]);
+        let actual_games = parse_input(&input[..]).unwrap();
         let actual_game = actual_games.first().unwrap();
         let actual_sample = actual_game.samples.first().unwrap();
 
@@ -157,6 +216,31 @@ mod tests {
         assert_eq!(*actual_sample, expected_sample);
     }
 
+    #[test]
+    fn test_parse_input_with_any_color() {
+        let input = b"Game 1: 3 blue, 4 red\n\
+                     Game 2: 1 blue, 2 purple";
+        let games = parse_input(&input[..]).unwrap();
+
+        assert_eq!(games[1].samples[0], CubeSample::from([("blue", 1), ("purple", 2)]));
+        assert_eq!(sum_possible_ids(&games, &reference_bag()), 1);
+        assert_eq!(sum_possible_ids(&games, &CubeSample::from([("blue", 3), ("purple", 2), ("red", 4)])), 3);
+    }
+
+    #[test]
+    fn test_parse_input_without_color() {
+        let input = b"Game 1: 3 blue, 4 red\n\
+                     Game 2: 1 blue, 2 7";
+        let expected = ParseError::Syntax {
+            day: 2,
+            line: 2,
+            column: 19,
+            text: String::from("7"),
+            expected: "a color name",
+        };
+        assert_eq!(parse_input(&input[..]).unwrap_err(), expected);
+    }
+
     #[test]
     fn test_solve() {
             let input = b"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
@@ -164,10 +248,48 @@ mod tests {
                                       Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\n\
                                       Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\n\
                                       Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-            let mut output = Vec::new();
 
-            solve(&input[..], &mut output);
+        assert_eq!(solve(&input[..], &reference_bag()), Ok(Answer::Unsigned(8)));
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let game = parse_game(&Line::new(DAY, 0, line)).unwrap();
+        assert_eq!(game.to_string(), "Game 1: 3 blue, 4 red; 6 blue, 2 green, 1 red; 2 green");
+    }
+
+    // xorshift64, enough to generate games without extra dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+    }
 
-        assert_eq!(String::from_utf8(output).unwrap(), "The sum of all possible game IDs is: 8");
+    fn random_game(rng: &mut Rng) -> Game {
+        const COLORS: [&str; 6] = ["blue", "green", "red", "teal", "amber", "violet"];
+        let samples = (0..1 + rng.below(5))
+            .map(|_| {
+                (0..1 + rng.below(COLORS.len() as u64))
+                    .map(|_| (COLORS[rng.below(COLORS.len() as u64) as usize], 1 + rng.below(50) as u32))
+                    .collect()
+            })
+            .collect();
+        Game { id: rng.below(1000) as u32, samples }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let mut rng = Rng(0x2023_1202);
+        for _ in 0..500 {
+            let game = random_game(&mut rng);
+            let text = game.to_string();
+            assert_eq!(parse_game(&Line::new(DAY, 0, &text)), Ok(game), "{}", text);
+        }
     }
 }
@@ -32,4 +32,87 @@ five powers produces the sum 2286.
 
 For each game, find the minimum set of cubes that must have been present. What
 is the sum of the power of these sets?
-*/
\ No newline at end of file
+*/
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+use super::p1::{parse_input, CubeSample, Game};
+use super::DAY;
+
+/// Fewest cubes of each color that make the game possible, i.e. the
+/// element-wise maximum of its samples.
+pub fn minimum_set(game: &Game) -> CubeSample {
+    let mut min_set = CubeSample::new();
+    for (color, count) in game.samples.iter().flat_map(CubeSample::iter) {
+        min_set.set(color, min_set.get(color).max(count));
+    }
+    min_set
+}
+
+/// Product of the cubes of every color in `palette`, which is zero if any of
+/// them is missing from the sample, or `None` if it does not fit in 64 bits.
+pub fn power(sample: &CubeSample, palette: &BTreeSet<&str>) -> Option<u64> {
+    palette.iter().try_fold(1_u64, |acc, color| acc.checked_mul(u64::from(sample.get(color))))
+}
+
+/// Sum of the powers of `games`, one per line of input. The palette grows
+/// with every color seen, so a game whose power pushes the sum past 64 bits
+/// is reported as an error on its line.
+pub fn sum_powers(games: &[Game]) -> Result<u64, ParseError> {
+    let palette: BTreeSet<&str> = games
+        .iter()
+        .flat_map(|g| g.samples.iter().flat_map(CubeSample::colors))
+        .collect();
+    games.iter().enumerate().try_fold(0_u64, |sum, (idx, game)| {
+        power(&minimum_set(game), &palette)
+            .and_then(|p| sum.checked_add(p))
+            .ok_or_else(|| {
+                let line = game.to_string();
+                ParseError::syntax(DAY, idx, &line, &line, "a power that fits in 64 bits")
+            })
+    })
+}
+
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = sum_powers(&parse_input(reader)?)?;
+
+    Ok(solution.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::example;
+
+    use super::*;
+
+    #[test]
+    fn test_minimum_set() {
+        let games = parse_input(&b"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"[..]).unwrap();
+        let expected = CubeSample::from([("blue", 6), ("green", 2), ("red", 4)]);
+        assert_eq!(minimum_set(&games[0]), expected);
+        assert_eq!(power(&expected, &BTreeSet::from(["blue", "green", "red"])), Some(48));
+        assert_eq!(power(&expected, &BTreeSet::from(["blue", "purple"])), Some(0));
+    }
+
+    #[test]
+    fn test_powers_of_many_colors() {
+        let colors = ["red", "green", "blue", "cyan", "teal", "pink", "gray", "gold", "navy", "lime", "plum", "rose", "sand", "jade"];
+        let game = |count: usize| {
+            let sample: Vec<String> = colors[..count].iter().map(|c| format!("30 {}", c)).collect();
+            format!("Game 1: {}", sample.join(", "))
+        };
+        // 30^7 no longer fits in 32 bits, 30^14 does not fit in 64.
+        assert_eq!(solve(game(7).as_bytes()), Ok(Answer::Unsigned(21870000000)));
+        assert_eq!(solve(game(14).as_bytes()).unwrap_err().line(), 1);
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = example(include_str!("p2.rs"), "example games from earlier");
+
+        assert_eq!(solve(input.as_bytes()), Ok(Answer::Unsigned(2286)));
+    }
+}
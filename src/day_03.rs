@@ -1 +1,46 @@
-pub mod p1;
\ No newline at end of file
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
+pub mod graph;
+pub mod p1;
+pub mod p2;
+pub mod stream;
+
+pub const DAY: u8 = 3;
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Input = p1::Schematic;
+    type Output = u64;
+
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead {
+        p1::parse_input(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        p1::sum_parts(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(p2::sum_gear_ratios(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{example, example_tests};
+
+    use super::*;
+
+    example_tests! {
+        solver: Day03,
+        schematic => {
+            input: example(include_str!("day_03/p1.rs"), "Here is an example engine schematic:"),
+            part1: 4361,
+            part2: 467835,
+        },
+    }
+}
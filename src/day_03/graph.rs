@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::grid::Position;
+
+use super::p1::{Schematic, SchematicNumber};
+
+/// Bipartite graph linking every symbol of a schematic to the numbers it
+/// touches. Numbers are identified by their index in `Schematic::numbers`.
+#[derive(Debug, Clone)]
+pub struct SchematicGraph<'a> {
+    schematic: &'a Schematic,
+    symbols: Vec<(Position, char)>,
+    by_position: HashMap<Position, usize>,
+    symbol_numbers: Vec<Vec<usize>>,
+    number_symbols: Vec<Vec<usize>>,
+}
+
+/// Symbols and numbers connected through adjacency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component<'a> {
+    pub symbols: Vec<(Position, char)>,
+    pub numbers: Vec<&'a SchematicNumber>,
+}
+
+impl<'a> SchematicGraph<'a> {
+    pub fn new(schematic: &'a Schematic) -> SchematicGraph<'a> {
+        let symbols: Vec<(Position, char)> = schematic.symbols().collect();
+        let by_position = symbols.iter().enumerate().map(|(idx, (position, _))| (*position, idx)).collect();
+        let mut number_symbols = vec![Vec::new(); schematic.numbers().len()];
+        let symbol_numbers = symbols
+            .iter()
+            .enumerate()
+            .map(|(symbol, (position, _))| {
+                let attached: Vec<usize> = schematic.number_indices_near(*position).collect();
+                for number in attached.iter() {
+                    number_symbols[*number].push(symbol);
+                }
+                attached
+            })
+            .collect();
+        SchematicGraph { schematic, symbols, by_position, symbol_numbers, number_symbols }
+    }
+
+    /// Numbers attached to the symbol at `position`, or `None` if there is no
+    /// symbol there.
+    pub fn numbers_at(&self, position: Position) -> Option<Vec<&'a SchematicNumber>> {
+        let symbol = *self.by_position.get(&position)?;
+        Some(self.symbol_numbers[symbol].iter().map(|n| &self.schematic.numbers()[*n]).collect())
+    }
+
+    /// Symbols touching exactly `count` numbers, e.g. 2 for gear candidates.
+    pub fn symbols_with(&self, count: usize) -> Vec<(Position, char)> {
+        self.symbols
+            .iter()
+            .zip(self.symbol_numbers.iter())
+            .filter(|(_, numbers)| numbers.len() == count)
+            .map(|(symbol, _)| *symbol)
+            .collect()
+    }
+
+    /// Connected groups of symbols and part numbers. Numbers and symbols that
+    /// touch nothing are left out.
+    pub fn components(&self) -> Vec<Component<'a>> {
+        let numbers = self.schematic.numbers();
+        let mut seen_symbols = vec![false; self.symbols.len()];
+        let mut seen_numbers = vec![false; numbers.len()];
+        let mut components = Vec::new();
+        for start in 0..self.symbols.len() {
+            if seen_symbols[start] || self.symbol_numbers[start].is_empty() {
+                continue;
+            }
+            seen_symbols[start] = true;
+            let mut component = Component { symbols: Vec::new(), numbers: Vec::new() };
+            let mut stack = vec![start];
+            while let Some(symbol) = stack.pop() {
+                component.symbols.push(self.symbols[symbol]);
+                for &number in self.symbol_numbers[symbol].iter() {
+                    if seen_numbers[number] {
+                        continue;
+                    }
+                    seen_numbers[number] = true;
+                    component.numbers.push(&numbers[number]);
+                    for &next in self.number_symbols[number].iter() {
+                        if !seen_symbols[next] {
+                            seen_symbols[next] = true;
+                            stack.push(next);
+                        }
+                    }
+                }
+            }
+            component.symbols.sort_unstable();
+            component.numbers.sort_unstable_by_key(|n| (n.row, n.columns.start));
+            components.push(component);
+        }
+        components
+    }
+
+    /// Graphviz rendering of the graph, see `Dot`.
+    pub fn dot(&self) -> Dot<'_, 'a> {
+        Dot(self)
+    }
+}
+
+/// Undirected DOT graph with a box per symbol and an ellipse per number,
+/// labelled with their contents and (row, column).
+pub struct Dot<'g, 'a>(&'g SchematicGraph<'a>);
+
+impl fmt::Display for Dot<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let graph = self.0;
+        writeln!(f, "graph schematic {{")?;
+        for (idx, ((row, col), symbol)) in graph.symbols.iter().enumerate() {
+            writeln!(f, "    s{} [shape=box, label=\"{} ({}, {})\"];", idx, symbol.escape_default(), row, col)?;
+        }
+        for (idx, number) in graph.schematic.numbers().iter().enumerate() {
+            writeln!(f, "    n{} [label=\"{} ({}, {})\"];", idx, number.number, number.row, number.columns.start)?;
+        }
+        for (symbol, numbers) in graph.symbol_numbers.iter().enumerate() {
+            for number in numbers {
+                writeln!(f, "    s{} -- n{};", symbol, number)?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::example;
+
+    use super::super::p1::parse_input;
+    use super::*;
+
+    fn numbers(numbers: &[&SchematicNumber]) -> Vec<u32> {
+        numbers.iter().map(|n| n.number).collect()
+    }
+
+    #[test]
+    fn test_queries() {
+        let input = example(include_str!("p1.rs"), "Here is an example engine schematic:");
+        let schematic = parse_input(input.as_bytes()).unwrap();
+        let graph = SchematicGraph::new(&schematic);
+
+        assert_eq!(graph.numbers_at((1, 3)).map(|n| numbers(&n)), Some(vec![467, 35]));
+        assert_eq!(graph.numbers_at((0, 0)), None);
+        assert_eq!(graph.symbols_with(2), vec![((1, 3), '*'), ((8, 5), '*')]);
+        assert_eq!(graph.symbols_with(0), vec![]);
+    }
+
+    #[test]
+    fn test_components() {
+        let schematic = parse_input(&b"1*2.3\n....$\n7#..4"[..]).unwrap();
+        let graph = SchematicGraph::new(&schematic);
+        let components = graph.components();
+
+        assert_eq!(components.len(), 3);
+        assert_eq!(numbers(&components[0].numbers), vec![1, 2]);
+        assert_eq!(components[1].symbols, vec![((1, 4), '$')]);
+        assert_eq!(numbers(&components[1].numbers), vec![3, 4]);
+        assert_eq!(numbers(&components[2].numbers), vec![7]);
+    }
+
+    #[test]
+    fn test_dot() {
+        let schematic = parse_input(&b"12*\n..3"[..]).unwrap();
+        let dot = SchematicGraph::new(&schematic).dot().to_string();
+        assert_eq!(
+            dot,
+            "graph schematic {\n    s0 [shape=box, label=\"* (0, 2)\"];\n    \
+             n0 [label=\"12 (0, 0)\"];\n    n1 [label=\"3 (1, 2)\"];\n    \
+             s0 -- n0;\n    s0 -- n1;\n}\n"
+        );
+    }
+}
@@ -1,6 +1,12 @@
-use regex::Regex;
-// use std::io::{BufRead, Write};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
+use std::io::BufRead;
+use std::ops::Range;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::grid::{Grid, Position};
+
+use super::DAY;
 
 /*
 --- Day 3: Gear Ratios ---
@@ -48,104 +54,288 @@ Of course, the actual engine schematic is much larger. What is the sum of all of
 the part numbers in the engine schematic?
 */
 
-#[derive(Debug, PartialEq)]
-pub struct SchematicLine {
-    pub candidates: Vec<SchematicNumber>,
-    pub symbol_locations: HashSet<usize>,
+/// Which characters of a schematic count as symbols.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Symbols {
+    /// Anything but digits and periods.
+    #[default]
+    Any,
+    /// Only the given characters.
+    Only(HashSet<char>),
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct SchematicNumber {
-    pub number: u32,
-    pub adjacency_range: HashSet<usize>,
+impl Symbols {
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            Symbols::Any => !c.is_ascii_digit() && c != '.',
+            Symbols::Only(symbols) => symbols.contains(&c),
+        }
+    }
 }
 
-fn parse_line(line: &str) -> SchematicLine {
-    let number_regex = Regex::new(r"\d+",).unwrap();
-    let numbers: Vec<SchematicNumber> = number_regex.find_iter(line).map(|n| {
-        let mut range = n.range();
-        if range.start > 0 {
-            range.start -= 1;
-        };
-
-        if range.end < line.len() - 1 {
-            range.end += 1;
-        };
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Digit(u8),
+    Symbol(char),
+}
 
-        SchematicNumber {
-            number: n.as_str().parse::<u32>().unwrap(),
-            adjacency_range: HashSet::from_iter(range),
+impl From<&Cell> for char {
+    fn from(cell: &Cell) -> char {
+        match cell {
+            Cell::Empty => '.',
+            Cell::Digit(d) => char::from(b'0' + d),
+            Cell::Symbol(s) => *s,
         }
-    }).collect();
+    }
+}
 
-    let symbol_regex = Regex::new(r"[*#+$]").unwrap();
+/// A run of digits in a single row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchematicNumber {
+    pub number: u32,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
 
-    let symbol_locations = symbol_regex.find_iter(line).map(|n| {
-        n.range().start
-    }).collect();
+impl SchematicNumber {
+    /// Whether `position` lies within the box one cell around the number,
+    /// including diagonals.
+    pub fn is_adjacent(&self, (row, col): Position) -> bool {
+        row + 1 >= self.row && row <= self.row + 1 && col + 1 >= self.columns.start && col <= self.columns.end
+    }
 
-    SchematicLine {
-        candidates: numbers,
-        symbol_locations,
+    /// Positions of the box around the number, clipped to a grid of
+    /// `height` by `width` cells. Includes the digits themselves.
+    pub fn surroundings(&self, height: usize, width: usize) -> impl Iterator<Item = Position> {
+        let rows = self.row.saturating_sub(1)..(self.row + 2).min(height);
+        let columns = self.columns.start.saturating_sub(1)..(self.columns.end + 1).min(width);
+        rows.flat_map(move |row| columns.clone().map(move |col| (row, col)))
     }
 }
 
-fn is_symbol_adjacent(number: &SchematicNumber, line_idx: usize, lines: &Vec<SchematicLine>) -> bool {
-    !lines[line_idx].symbol_locations.is_disjoint(&number.adjacency_range)
-    || line_idx > 0 && is_symbol_adjacent(number, line_idx - 1, lines)
-    || line_idx < lines.len() - 1 && is_symbol_adjacent(number, line_idx + 1, lines)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    grid: Grid<Cell>,
+    // row by row, so the numbers of a row are contiguous.
+    numbers: Vec<SchematicNumber>,
+    rows: Vec<Range<usize>>,
 }
 
-pub fn find_parts(lines: Vec<SchematicLine>) -> Vec<SchematicNumber> {
-    let mut parts: Vec<SchematicNumber> = Vec::new();
-    for (line_idx, line) in lines.iter().enumerate() {
-        for candidate in line.candidates.iter() {
-            if is_symbol_adjacent(candidate, line_idx, &lines) {
-                parts.push(candidate.clone());
+impl Schematic {
+    pub fn new(grid: Grid<Cell>) -> Result<Schematic, ParseError> {
+        let mut numbers = Vec::new();
+        let mut rows = Vec::with_capacity(grid.height());
+        for (row, cells) in grid.rows().enumerate() {
+            let first = numbers.len();
+            let mut col = 0;
+            while col < cells.len() {
+                let Cell::Digit(_) = cells[col] else {
+                    col += 1;
+                    continue;
+                };
+                let start = col;
+                let mut number: Option<u32> = Some(0);
+                while let Some(Cell::Digit(d)) = cells.get(col) {
+                    number = number.and_then(|n| n.checked_mul(10)?.checked_add(u32::from(*d)));
+                    col += 1;
+                }
+                let number = number.ok_or_else(|| {
+                    let line: String = cells.iter().map(char::from).collect();
+                    // cells are chars, the fragment needs byte offsets.
+                    let byte = |col| line.char_indices().nth(col).map_or(line.len(), |(b, _)| b);
+                    ParseError::syntax(DAY, row, &line, &line[byte(start)..byte(col)], "a number")
+                })?;
+                numbers.push(SchematicNumber { number, row, columns: start..col });
             }
+            rows.push(first..numbers.len());
         }
+        Ok(Schematic { grid, numbers, rows })
+    }
+
+    pub fn grid(&self) -> &Grid<Cell> {
+        &self.grid
+    }
+
+    pub fn numbers(&self) -> &[SchematicNumber] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = (Position, char)> + '_ {
+        self.grid.iter().filter_map(|(position, cell)| match cell {
+            Cell::Symbol(s) => Some((position, *s)),
+            _ => None,
+        })
+    }
+
+    /// Indices in `numbers` of the numbers touching `position`, looking only
+    /// at the rows around it.
+    pub fn number_indices_near(&self, position: Position) -> impl Iterator<Item = usize> + '_ {
+        let rows = position.0.saturating_sub(1)..(position.0 + 2).min(self.rows.len());
+        let range = if rows.is_empty() {
+            0..0
+        } else {
+            self.rows[rows.start].start..self.rows[rows.end - 1].end
+        };
+        range.filter(move |idx| self.numbers[*idx].is_adjacent(position))
+    }
+
+    /// Numbers touching `position`.
+    pub fn numbers_near(&self, position: Position) -> impl Iterator<Item = &SchematicNumber> {
+        self.number_indices_near(position).map(|idx| &self.numbers[idx])
+    }
+
+    /// Symbols touching `number`.
+    pub fn symbols_near<'a>(&'a self, number: &SchematicNumber) -> impl Iterator<Item = (Position, char)> + 'a {
+        number
+            .surroundings(self.grid.height(), self.grid.width())
+            .filter_map(|position| match self.grid[position] {
+                Cell::Symbol(s) => Some((position, s)),
+                _ => None,
+            })
     }
-    parts
+}
+
+/// A part number with the kinds of symbols it touches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    pub number: u32,
+    pub symbols: BTreeSet<char>,
+}
+
+pub fn classify_parts(schematic: &Schematic) -> Vec<Part> {
+    schematic
+        .numbers()
+        .iter()
+        .map(|n| Part { number: n.number, symbols: schematic.symbols_near(n).map(|(_, s)| s).collect() })
+        .filter(|p| !p.symbols.is_empty())
+        .collect()
+}
+
+pub fn find_parts(schematic: &Schematic) -> Vec<&SchematicNumber> {
+    schematic
+        .numbers()
+        .iter()
+        .filter(|n| schematic.symbols_near(n).next().is_some())
+        .collect()
+}
+
+pub fn parse_input<R>(reader: R) -> Result<Schematic, ParseError> where R: BufRead {
+    parse_input_with(reader, &Symbols::default())
+}
+
+pub fn parse_input_with<R>(reader: R, symbols: &Symbols) -> Result<Schematic, ParseError> where R: BufRead {
+    let grid = Grid::parse(DAY, reader, |c| {
+        Some(match c.to_digit(10) {
+            Some(d) => Cell::Digit(d as u8),
+            None if symbols.contains(c) => Cell::Symbol(c),
+            None => Cell::Empty,
+        })
+    })?;
+    Schematic::new(grid)
+}
+
+pub fn sum_parts(schematic: &Schematic) -> u64 {
+    find_parts(schematic).iter().map(|p| u64::from(p.number)).sum()
+}
+
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = sum_parts(&parse_input(reader)?);
+
+    Ok(solution.into())
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::test_support::example;
+
     use super::*;
 
+    fn parts(input: &str) -> Vec<u32> {
+        find_parts(&parse_input(input.as_bytes()).unwrap()).iter().map(|p| p.number).collect()
+    }
+
     #[test]
-    fn test_parse_line() {
-        let line = "467..114..";
-        let expected = SchematicLine {
-            candidates: vec![
-                SchematicNumber {
-                    number: 467,
-                    adjacency_range: HashSet::from([0, 1, 2, 3]),
-                },
-                SchematicNumber {
-                    number: 114,
-                    adjacency_range: HashSet::from([4, 5, 6, 7, 8]),
-                }
-            ],
-            symbol_locations: HashSet::from([]),
+    fn test_parse_input() {
+        let schematic = parse_input(&b"467..114.."[..]).unwrap();
+        let expected = vec![
+            SchematicNumber { number: 467, row: 0, columns: 0..3 },
+            SchematicNumber { number: 114, row: 0, columns: 5..8 },
+        ];
+        assert_eq!(schematic.numbers(), expected);
+        assert_eq!(schematic.symbols().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_input_with_overflowing_number() {
+        let input = b".............\n\
+                     .99999999999*";
+        let expected = ParseError::Syntax {
+            day: 3,
+            line: 2,
+            column: 2,
+            text: String::from("99999999999"),
+            expected: "a number",
         };
-        let actual = parse_line(line);
-        assert_eq!(actual, expected);
+        assert_eq!(parse_input(&input[..]), Err(expected));
+        let error = parse_input("€99999999999*".as_bytes()).unwrap_err();
+        assert!(matches!(error, ParseError::Syntax { column: 4, .. }), "{:?}", error);
     }
 
     #[test]
     fn test_find_parts() {
-        // Visual schematic for the test
-        // 10*...
-        let number = SchematicNumber {
-            number: 10,
-            adjacency_range: HashSet::from([0, 1, 2]),
-        };
-        let line = SchematicLine {
-            candidates: vec![number.clone()],
-            symbol_locations: HashSet::from([2]),
-        };
-        let actual_parts = find_parts(vec![line]);
-        let actual_number  = actual_parts.first().unwrap();
-        assert_eq!(number, *actual_number);
+        assert_eq!(parts("10*..."), vec![10]);
+    }
+
+    #[test]
+    fn test_numbers_at_every_border() {
+        // numbers on each edge and corner, all around a single symbol.
+        assert_eq!(parts("123\n4*5\n678"), vec![123, 4, 5, 678]);
+        // ending one column before the edge, next to a symbol on the edge.
+        assert_eq!(parts("..12.\n....*"), vec![12]);
+        assert_eq!(parts("....*\n..12."), vec![12]);
+        // ending on the edge.
+        assert_eq!(parts("...12\n..*.."), vec![12]);
+        assert_eq!(parts("#....\n.12.."), vec![12]);
+        // a single row or column.
+        assert_eq!(parts("1*2.3"), vec![1, 2]);
+        assert_eq!(parts("7\n*\n3\n.\n4"), vec![7, 3]);
+    }
+
+    #[test]
+    fn test_numbers_away_from_symbols() {
+        assert_eq!(parts("1..\n...\n..*"), Vec::<u32>::new());
+        assert_eq!(parts("12.\n...\n*.."), Vec::<u32>::new());
+        assert_eq!(parts("..*\n...\n.12"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_numbers_near() {
+        let schematic = parse_input(&b"1.2\n.*.\n3.4\n5.."[..]).unwrap();
+        let near: Vec<u32> = schematic.numbers_near((1, 1)).map(|n| n.number).collect();
+        assert_eq!(near, vec![1, 2, 3, 4]);
+        assert_eq!(schematic.numbers_near((3, 2)).map(|n| n.number).collect::<Vec<_>>(), vec![4]);
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = example(include_str!("p1.rs"), "Here is an example engine schematic:");
+
+        assert_eq!(solve(input.as_bytes()), Ok(Answer::Unsigned(4361)));
+    }
+
+    #[test]
+    fn test_symbol_classes() {
+        let input = b"1.2/3\n\
+                     @.%..\n\
+                     4-5..";
+        let schematic = parse_input(&input[..]).unwrap();
+        let parts = classify_parts(&schematic);
+        assert_eq!(parts.iter().map(|p| p.number).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(parts[1].symbols, BTreeSet::from(['%', '/']));
+
+        let only = Symbols::Only(HashSet::from(['@']));
+        let schematic = parse_input_with(&input[..], &only).unwrap();
+        assert_eq!(sum_parts(&schematic), 1 + 4);
     }
 }
@@ -0,0 +1,98 @@
+/*
+--- Part Two ---
+The engineer finds the missing part and installs it in the engine! As the
+engine springs to life, you jump in the closest gondola, finally ready to
+ascend to the water source.
+
+You don't seem to be going very fast, though. Maybe something is still wrong?
+Fortunately, the gondola has a phone labeled "help", so you pick it up and the
+engineer answers.
+
+Before you can explain the situation, she suggests that you look out the
+window. There stands the engineer, holding a phone in one hand and waving with
+the other. You're going so slowly that you haven't even left the station. You
+exit the gondola.
+
+The missing part wasn't the only issue - one of the gears in the engine is
+wrong. A gear is any * symbol that is adjacent to exactly two part numbers. Its
+gear ratio is the result of multiplying those two numbers together.
+
+This time, you need to find the gear ratio of every gear and add them all up so
+that the engineer can figure out which gear needs to be replaced.
+
+Consider the same engine schematic again:
+
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+
+In this schematic, there are two gears. The first is in the top left; it has
+part numbers 467 and 35, so its gear ratio is 16345. The second gear is in the
+lower right; its gear ratio is 451490. (The * adjacent to 617 is not a gear
+because it is only adjacent to one part number.) Adding up all of the gear
+ratios produces 467835.
+
+What is the sum of all of the gear ratios in your engine schematic?
+*/
+
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+use super::p1::{parse_input, Schematic};
+
+/// Products of the two numbers next to every `*` touching exactly two. Each
+/// number fits in 32 bits, so their product fits in 64.
+pub fn gear_ratios(schematic: &Schematic) -> Vec<u64> {
+    schematic
+        .symbols()
+        .filter(|(_, s)| *s == '*')
+        .filter_map(|(position, _)| {
+            let numbers: Vec<u64> = schematic.numbers_near(position).map(|n| u64::from(n.number)).collect();
+            match numbers[..] {
+                [a, b] => Some(a * b),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+pub fn sum_gear_ratios(schematic: &Schematic) -> u64 {
+    gear_ratios(schematic).iter().sum()
+}
+
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = sum_gear_ratios(&parse_input(reader)?);
+
+    Ok(solution.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::example;
+
+    use super::*;
+
+    #[test]
+    fn test_gear_ratios() {
+        let input = example(include_str!("p1.rs"), "Here is an example engine schematic:");
+        let schematic = parse_input(input.as_bytes()).unwrap();
+
+        assert_eq!(gear_ratios(&schematic), vec![16345, 451490]);
+        assert_eq!(solve(input.as_bytes()), Ok(Answer::Unsigned(467835)));
+    }
+
+    #[test]
+    fn test_gear_ratio_beyond_32_bits() {
+        let input = b"99999*99999";
+        assert_eq!(solve(&input[..]), Ok(Answer::Unsigned(9999800001)));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use crate::error::ParseError;
+
+use super::p1::{SchematicNumber, Symbols};
+use super::DAY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Part(u32),
+    GearRatio(u64),
+}
+
+#[derive(Debug)]
+struct Row {
+    idx: usize,
+    numbers: Vec<SchematicNumber>,
+    symbols: Vec<(usize, char)>,
+}
+
+impl Row {
+    fn parse(idx: usize, line: &str, symbols: &Symbols) -> Result<Row, ParseError> {
+        let mut row = Row { idx, numbers: Vec::new(), symbols: Vec::new() };
+        let mut chars = line.char_indices().enumerate().peekable();
+        while let Some((col, (byte, c))) = chars.next() {
+            if !c.is_ascii_digit() {
+                if symbols.contains(c) {
+                    row.symbols.push((col, c));
+                }
+                continue;
+            }
+            let mut end = (col + 1, byte + 1);
+            while let Some((col, (byte, _))) = chars.next_if(|(_, (_, c))| c.is_ascii_digit()) {
+                end = (col + 1, byte + 1);
+            }
+            let digits = &line[byte..end.1];
+            let number = digits.parse().map_err(|_| ParseError::syntax(DAY, idx, line, digits, "a number"))?;
+            row.numbers.push(SchematicNumber { number, row: idx, columns: col..end.0 });
+        }
+        Ok(row)
+    }
+}
+
+/// Scans a schematic line by line, keeping only the rows around the one being
+/// examined. Part numbers and gear ratios of a row are emitted as soon as the
+/// row below it has been read, in the order they appear.
+pub struct Scanner<R> {
+    reader: R,
+    symbols: Symbols,
+    buf: String,
+    window: VecDeque<Row>,
+    // index in `window` of the next row to examine.
+    next: usize,
+    events: VecDeque<Event>,
+    done: bool,
+}
+
+impl<R> Scanner<R> where R: BufRead {
+    pub fn new(reader: R, symbols: Symbols) -> Scanner<R> {
+        Scanner {
+            reader,
+            symbols,
+            buf: String::new(),
+            window: VecDeque::with_capacity(3),
+            next: 0,
+            events: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn read_row(&mut self) -> Result<Option<Row>, ParseError> {
+        let idx = self.window.back().map_or(0, |r| r.idx + 1);
+        self.buf.clear();
+        if self.reader.read_line(&mut self.buf).map_err(|e| ParseError::io(DAY, idx, e))? == 0 {
+            return Ok(None);
+        }
+        let line = self.buf.trim_end_matches(['\n', '\r']);
+        Row::parse(idx, line, &self.symbols).map(Some)
+    }
+
+    // queues the events of the row at `self.next`, whose neighbours are all
+    // in the window by now.
+    fn examine(&mut self) {
+        let row = &self.window[self.next];
+        let symbol_positions = || self.window.iter().flat_map(|r| r.symbols.iter().map(move |(c, _)| (r.idx, *c)));
+        for number in row.numbers.iter() {
+            if symbol_positions().any(|position| number.is_adjacent(position)) {
+                self.events.push_back(Event::Part(number.number));
+            }
+        }
+        for &(col, symbol) in row.symbols.iter() {
+            if symbol != '*' {
+                continue;
+            }
+            let mut near = self
+                .window
+                .iter()
+                .flat_map(|r| r.numbers.iter())
+                .filter(|n| n.is_adjacent((row.idx, col)));
+            if let (Some(a), Some(b), None) = (near.next(), near.next(), near.next()) {
+                self.events.push_back(Event::GearRatio(u64::from(a.number) * u64::from(b.number)));
+            }
+        }
+        self.next += 1;
+    }
+
+    fn advance(&mut self) -> Result<(), ParseError> {
+        match self.read_row()? {
+            Some(row) => {
+                if self.window.len() == 3 {
+                    self.window.pop_front();
+                    self.next -= 1;
+                }
+                self.window.push_back(row);
+                if self.next + 1 < self.window.len() {
+                    self.examine();
+                }
+            }
+            None => {
+                if self.next < self.window.len() {
+                    self.examine();
+                }
+                self.done = true;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R> Iterator for Scanner<R> where R: BufRead {
+    type Item = Result<Event, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.events.is_empty() && !self.done {
+            if let Err(error) = self.advance() {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+        self.events.pop_front().map(Ok)
+    }
+}
+
+/// Sums of the part numbers and gear ratios of a schematic, read with bounded
+/// memory.
+pub fn sums<R>(reader: R, symbols: Symbols) -> Result<(u64, u64), ParseError> where R: BufRead {
+    let mut sums = (0, 0);
+    for event in Scanner::new(reader, symbols) {
+        match event? {
+            Event::Part(number) => sums.0 += u64::from(number),
+            Event::GearRatio(ratio) => sums.1 += ratio,
+        }
+    }
+    Ok(sums)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::example;
+
+    use super::super::p1::{find_parts, parse_input};
+    use super::super::p2::gear_ratios;
+    use super::*;
+
+    fn schematic() -> String {
+        example(include_str!("p1.rs"), "Here is an example engine schematic:")
+    }
+
+    #[test]
+    fn test_sums() {
+        assert_eq!(sums(schematic().as_bytes(), Symbols::default()), Ok((4361, 467835)));
+    }
+
+    #[test]
+    fn test_events_follow_rows() {
+        let events: Vec<Event> = Scanner::new(schematic().as_bytes(), Symbols::default()).map(Result::unwrap).collect();
+        assert_eq!(&events[..4], &[Event::Part(467), Event::GearRatio(16345), Event::Part(35), Event::Part(633)]);
+    }
+
+    #[test]
+    fn test_matches_grid_solver() {
+        let inputs: [&[u8]; 5] = [b"", b"1*", b"..12.\n....*", b"7\n*\n3\n.\n4", b"12.\n.*.\n.34\n*..\n5.."];
+        for input in inputs {
+            let schematic = parse_input(input).unwrap();
+            let parts: u64 = find_parts(&schematic).iter().map(|p| u64::from(p.number)).sum();
+            let ratios: u64 = gear_ratios(&schematic).iter().sum();
+            assert_eq!(sums(input, Symbols::default()), Ok((parts, ratios)));
+        }
+    }
+
+    #[test]
+    fn test_gear_ratio_beyond_32_bits() {
+        assert_eq!(sums(&b"99999*99999"[..], Symbols::default()), Ok((199998, 9999800001)));
+    }
+
+    #[test]
+    fn test_overflowing_number() {
+        let error = sums(&b"1*\n99999999999"[..], Symbols::default()).unwrap_err();
+        assert_eq!(error.line(), 2);
+    }
+}
@@ -1,2 +1,47 @@
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
 pub mod p1;
-pub mod p2;
\ No newline at end of file
+pub mod p2;
+
+pub const DAY: u8 = 4;
+
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Input = Vec<p1::ScratchCard>;
+    type Output = u64;
+
+    // card counts are checked here, as the parts cannot fail.
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead {
+        let cards = p1::parse_input(reader)?;
+        p2::total_cards(&cards)?;
+        Ok(cards)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        u64::from(p1::total_points(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(p2::total_cards(input).expect("Parsed cards to have counts that fit in 64 bits"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{example, example_tests};
+
+    use super::*;
+
+    example_tests! {
+        solver: Day04,
+        cards => {
+            input: example(include_str!("day_04/p1.rs"), "For example:"),
+            part1: 13,
+            part2: 30,
+        },
+    }
+}
@@ -1,6 +1,11 @@
-use regex::Regex;
 use std::collections::HashSet;
-use std::io::{BufRead, Write};
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::parse::Line;
+
+use super::DAY;
 
 /*
 --- Day 4: Scratchcards ---
@@ -70,44 +75,30 @@ pub struct ScratchCard {
     winning_numbers: HashSet<u32>,
 }
 
-fn count_winning(card: &ScratchCard) -> usize {
+pub(crate) fn count_winning(card: &ScratchCard) -> usize {
     card.numbers.iter().filter(|n| card.winning_numbers.contains(n)).count()
 }
 
-fn parse_card(card: &str) -> ScratchCard {
-    let mut header_split = card.split(':');
-    // discard card header
-    header_split.next().expect("Card header to be present");
-    let card_contents = header_split.next().expect("Card contents to be present");
-    let mut contents_split = card_contents.split('|');
-    let winning_numbers = contents_split.next().expect("Winning numbers to be present");
-    let numbers = contents_split.next().expect("Card numbers to be present");
-
-    let regex = Regex::new(r"\d+",).unwrap();
-    let winning_numbers: Vec<u32> = regex
-        .find_iter(winning_numbers)
-        .map(|n| {
-            n.as_str().parse::<u32>().expect("Number to be parseable")
-        }).collect();
-
-    let numbers = regex
-        .find_iter(numbers)
-        .map(|n| {
-            n.as_str().parse::<u32>().expect("Number to be parseable")
-        }).collect();
-
-    ScratchCard { numbers, winning_numbers: HashSet::from_iter(winning_numbers) }
+fn parse_card(line_idx: usize, card: &str) -> Result<ScratchCard, ParseError> {
+    let line = Line::new(DAY, line_idx, card);
+    let (_, card_contents) = line.header::<u32>("Card")?;
+    let (winning_numbers, numbers) = line.split_once(card_contents, '|', "numbers separated by '|'")?;
+
+    Ok(ScratchCard {
+        numbers: line.unsigned(numbers)?,
+        winning_numbers: line.unsigned::<u32>(winning_numbers)?.into_iter().collect(),
+    })
 }
 
-fn parse_input<R>(reader: R) -> Vec<ScratchCard> where R: BufRead {
-    reader.lines().map(|l| {
-        let l = l.expect("Line to be present");
-        parse_card(&l)
+pub fn parse_input<R>(reader: R) -> Result<Vec<ScratchCard>, ParseError> where R: BufRead {
+    reader.lines().enumerate().map(|(idx, l)| {
+        let l = l.map_err(|e| ParseError::io(DAY, idx, e))?;
+        parse_card(idx, &l)
     }).collect()
 }
 
-pub fn solve<R, W>(reader: R, mut writer: W) where R: BufRead, W: Write {
-    let solution: u32 = parse_input(reader)
+pub fn total_points(cards: &[ScratchCard]) -> u32 {
+    cards
         .iter()
         .map(|c| {
             u32::try_from(count_winning(c))
@@ -122,9 +113,13 @@ pub fn solve<R, W>(reader: R, mut writer: W) where R: BufRead, W: Write {
             } else {
                 2_u32.pow(count - 1)
             }
-        }).sum();
+        }).sum()
+}
+
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = total_points(&parse_input(reader)?);
 
-    write!(&mut writer, "The pile of the Elf's scratchcards is worth: {}", solution).unwrap();
+    Ok(solution.into())
 }
 
 #[cfg(test)]
@@ -139,7 +134,21 @@ mod tests {
             numbers: vec![83, 86, 6, 31, 17, 9, 48, 53]
         };
 
-        assert_eq!(parse_card(line), expected);
+        assert_eq!(parse_card(0, line), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_card_without_separator() {
+        let line = "Card 1: 41 48 83 86 17";
+        let expected = ParseError::Syntax {
+            day: 4,
+            line: 1,
+            column: 8,
+            text: String::from(" 41 48 83 86 17"),
+            expected: "numbers separated by '|'",
+        };
+
+        assert_eq!(parse_card(0, line), Err(expected));
     }
 
     #[test]
@@ -45,4 +45,75 @@ scratchcards!
 Process all of the original and copied scratchcards until no more scratchcards
 are won. Including the original set of scratchcards, how many total scratchcards
 do you end up with?
-*/
\ No newline at end of file
+*/
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+use super::p1::{count_winning, parse_input, ScratchCard};
+use super::DAY;
+
+/// Number of cards held once every copy has been won. Copies only ever go to
+/// later cards, so a single pass in card order settles each count before it
+/// is used. Counts can double with every card, so the first card whose count
+/// does not fit in 64 bits is reported as an error on its line.
+pub fn total_cards(cards: &[ScratchCard]) -> Result<u64, ParseError> {
+    let overflow = |idx| ParseError::syntax(DAY, idx, "", "", "a card count that fits in 64 bits");
+    // copies won by the cards so far that still apply to the current card,
+    // and when they stop applying.
+    let mut copies: u64 = 0;
+    let mut expiring = vec![0_u64; cards.len() + 1];
+    let mut total: u64 = 0;
+    for (idx, card) in cards.iter().enumerate() {
+        copies -= expiring[idx];
+        let instances = copies.checked_add(1).ok_or_else(|| overflow(idx))?;
+        total = total.checked_add(instances).ok_or_else(|| overflow(idx))?;
+        let won = count_winning(card).min(cards.len() - idx - 1);
+        if won > 0 {
+            copies = copies.checked_add(instances).ok_or_else(|| overflow(idx))?;
+            expiring[idx + won + 1] += instances;
+        }
+    }
+    Ok(total)
+}
+
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError> where R: BufRead {
+    let solution = total_cards(&parse_input(reader)?)?;
+
+    Ok(solution.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::example;
+
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = example(include_str!("p1.rs"), "For example:");
+        assert_eq!(solve(input.as_bytes()), Ok(Answer::Unsigned(30)));
+    }
+
+    #[test]
+    fn test_copies_past_the_last_card() {
+        let input = b"Card 1: 1 2 | 1 2\nCard 2: 3 | 3";
+        assert_eq!(total_cards(&parse_input(&input[..]).unwrap()), Ok(3));
+    }
+
+    #[test]
+    fn test_copies_beyond_32_bits() {
+        // every card wins a copy of all the later ones, doubling the count.
+        let cards = |count: usize| {
+            let numbers: Vec<String> = (1..count).map(|n| n.to_string()).collect();
+            let numbers = numbers.join(" ");
+            let input: Vec<String> = (1..=count).map(|id| format!("Card {}: {} | {}", id, numbers, numbers)).collect();
+            parse_input(input.join("\n").as_bytes()).unwrap()
+        };
+        assert_eq!(total_cards(&cards(34)), Ok((1 << 34) - 1));
+        assert_eq!(total_cards(&cards(64)), Ok(u64::MAX));
+        // card 65 alone holds 2^64 instances.
+        assert_eq!(total_cards(&cards(65)).unwrap_err().line(), 65);
+    }
+}
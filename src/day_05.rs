@@ -1 +1,38 @@
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
 pub mod p1;
+
+pub const DAY: u8 = 5;
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input = p1::Almanac;
+    type Output = u64;
+
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead {
+        p1::parse_input(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        input.lowest_location().expect("Parsed almanac to list seeds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{example_tests, example_until};
+
+    use super::*;
+
+    example_tests! {
+        solver: Day05,
+        almanac => {
+            input: example_until(include_str!("day_05/p1.rs"), "For example:", "The almanac starts"),
+            part1: 35,
+        },
+    }
+}
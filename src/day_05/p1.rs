@@ -141,13 +141,19 @@ numbers?
 
 use std::{ops::Range, io::BufRead};
 
+use crate::error::ParseError;
+use crate::parse::{self, Section};
+
+use super::DAY;
+
+#[derive(Debug)]
 pub struct AlmanacMap {
     pub name: String,
     pub ranges: Vec<RangeMap>,
 }
 
 impl AlmanacMap {
-    fn get(&self, number: u32) -> u32 {
+    fn get(&self, number: u64) -> u64 {
         self.ranges.iter().find(|r| {
             r.source_range.contains(&number)
         }).map(|r| {
@@ -157,32 +163,72 @@ impl AlmanacMap {
     }
 }
 
+#[derive(Debug)]
 pub struct RangeMap {
-    pub source_range: Range<u32>,
-    pub destination_range: Range<u32>,
+    pub source_range: Range<u64>,
+    pub destination_range: Range<u64>,
     pub size: usize,
 }
 
+#[derive(Debug)]
 pub struct Almanac {
-    pub seeds: Vec<u32>,
+    pub seeds: Vec<u64>,
     pub map_chain: Vec<AlmanacMap>,
 }
 
 impl Almanac {
-    pub fn seed_location(&self, seed_number: u32) -> u32 {
+    pub fn seed_location(&self, seed_number: u64) -> u64 {
         self.map_chain.iter().fold(seed_number, |previous, almanac_map| {
             almanac_map.get(previous)
         })
     }
+
+    pub fn lowest_location(&self) -> Option<u64> {
+        self.seeds.iter().map(|s| self.seed_location(*s)).min()
+    }
 }
 
-pub fn parse_input<R>(reader: R) -> Almanac where R: BufRead {
+fn parse_map(section: &Section) -> Result<AlmanacMap, ParseError> {
+    let name = section
+        .label
+        .strip_suffix(" map")
+        .ok_or_else(|| section.header.error(section.label, "a map label"))?;
+    let ranges = section.lines.iter().map(|line| {
+        match line.unsigned::<u64>(line.text())?[..] {
+            [destination, source, size] => Ok(RangeMap {
+                source_range: source..source + size,
+                destination_range: destination..destination + size,
+                size: size as usize,
+            }),
+            _ => Err(line.error(line.text(), "a destination, source and size")),
+        }
+    }).collect::<Result<_, _>>()?;
+
+    Ok(AlmanacMap { name: String::from(name), ranges })
+}
 
-    todo!();
+pub fn parse_input<R>(reader: R) -> Result<Almanac, ParseError> where R: BufRead {
+    let text = parse::read_all(DAY, reader)?;
+    let sections = parse::sections(DAY, &text)?;
+    let (seeds, maps) = sections
+        .split_first()
+        .ok_or_else(|| ParseError::syntax(DAY, 0, "", "", "a seeds section"))?;
+    seeds.expect_label("seeds", "a seeds section")?;
+    let seed_numbers = seeds.header.unsigned(seeds.inline)?;
+    if seed_numbers.is_empty() {
+        return Err(seeds.header.error(seeds.inline, "at least one seed"));
+    }
+
+    Ok(Almanac {
+        seeds: seed_numbers,
+        map_chain: maps.iter().map(parse_map).collect::<Result<_, _>>()?,
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::test_support::example_until;
+
     use super::*;
 
     #[test]
@@ -251,5 +297,28 @@ mod tests {
     
     }
 
-}
+    #[test]
+    fn test_parse_input() {
+        let input = example_until(include_str!("p1.rs"), "For example:", "The almanac starts");
+        let almanac = parse_input(input.as_bytes()).unwrap();
+
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.map_chain.len(), 7);
+        assert_eq!(almanac.map_chain[0].name, "seed-to-soil");
+        let locations: Vec<u64> = almanac.seeds.iter().map(|s| almanac.seed_location(*s)).collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+        assert_eq!(almanac.lowest_location(), Some(35));
+    }
+
+    #[test]
+    fn test_parse_input_with_malformed_range() {
+        let input = b"seeds: 1\n\nseed-to-soil map:\n50 98\n";
+        assert_eq!(parse_input(&input[..]).unwrap_err().line(), 4);
+    }
 
+    #[test]
+    fn test_parse_input_without_seeds() {
+        let input = b"seeds:\n\nseed-to-soil map:\n50 98 2\n";
+        assert_eq!(parse_input(&input[..]).unwrap_err().line(), 1);
+    }
+}
@@ -1 +1,38 @@
-pub mod p1;
\ No newline at end of file
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
+pub mod p1;
+
+pub const DAY: u8 = 6;
+
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input = Vec<p1::Race>;
+    type Output = u64;
+
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead {
+        p1::parse_input(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        p1::margin_product(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{example_tests, example_until};
+
+    use super::*;
+
+    example_tests! {
+        solver: Day06,
+        races => {
+            input: example_until(include_str!("day_06/p1.rs"), "For example:", "This document"),
+            part1: 288,
+        },
+    }
+}
@@ -95,7 +95,15 @@ get if you multiply these numbers together?
 
 */
 
-struct Race {
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::parse::{self, Line};
+
+use super::DAY;
+
+#[derive(Debug, PartialEq)]
+pub struct Race {
     duration: u32,
     distance_record: u32,
 }
@@ -111,7 +119,13 @@ impl Boat {
     }
 }
 
+// the toy boat from the puzzle.
+const TOY_BOAT: Boat = Boat { initial_speed: 0, charging_factor: 1 };
+
 fn error_margin(boat: &Boat, race: &Race) -> u32 {
+    if race.duration == 0 {
+        return 0;
+    }
     let charging_time = race.duration / 2;
     let moving_time = race.duration - charging_time;
     let mut acc = 0;
@@ -132,6 +146,39 @@ fn error_margin(boat: &Boat, race: &Race) -> u32 {
     acc
 }
 
+fn parse_row<'a>(line: &Line<'a>, label: &str, expected: &'static str) -> Result<(Vec<u32>, &'a str), ParseError> {
+    let (name, values) = line.split_once(line.text(), ':', expected)?;
+    if name != label {
+        return Err(line.error(name, expected));
+    }
+    Ok((line.unsigned(values)?, values))
+}
+
+pub fn parse_input<R>(reader: R) -> Result<Vec<Race>, ParseError> where R: BufRead {
+    let text = parse::read_all(DAY, reader)?;
+    let mut lines = parse::lines(DAY, &text);
+    let (Some(times), Some(distances)) = (lines.next(), lines.next()) else {
+        let idx = text.lines().count();
+        return Err(ParseError::syntax(DAY, idx, "", "", "a 'Time:' and a 'Distance:' row"));
+    };
+    let (durations, _) = parse_row(&times, "Time", "a 'Time:' row")?;
+    let (records, fragment) = parse_row(&distances, "Distance", "a 'Distance:' row")?;
+    if durations.len() != records.len() {
+        return Err(distances.error(fragment, "as many distances as times"));
+    }
+
+    Ok(durations
+        .into_iter()
+        .zip(records)
+        .map(|(duration, distance_record)| Race { duration, distance_record })
+        .collect())
+}
+
+/// Product of the number of ways to beat the record of each race.
+pub fn margin_product(races: &[Race]) -> u64 {
+    races.iter().map(|r| u64::from(error_margin(&TOY_BOAT, r))).product()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +197,22 @@ mod tests {
 
         assert_eq!(error_margin(&boat, &race), 4);
     }
+
+    #[test]
+    fn test_parse_input() {
+        let input = b"Time:      7  15\nDistance:  9  40";
+        let expected = vec![
+            Race { duration: 7, distance_record: 9 },
+            Race { duration: 15, distance_record: 40 },
+        ];
+        assert_eq!(margin_product(&expected), 32);
+        assert_eq!(parse_input(&input[..]), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_input_with_missing_distance() {
+        let input = b"Time:      7  15\nDistance:  9";
+        assert_eq!(parse_input(&input[..]).unwrap_err().line(), 2);
+        assert!(parse_input(&b"Time: 7"[..]).is_err());
+    }
 }
@@ -1 +1,3 @@
-pub mod p1;
\ No newline at end of file
+pub mod p1;
+
+pub const DAY: u8 = 7;
@@ -1,5 +1,10 @@
 use std::{collections::HashMap, cmp::Ordering};
 
+use crate::error::ParseError;
+use crate::parse::Line;
+
+use super::DAY;
+
 #[derive(Debug, Eq, Hash, PartialEq)]
 pub enum Card {
     Ace,
@@ -58,19 +63,27 @@ pub struct Hand {
 
 use HandType::*;
 
-impl From<&str> for Hand {
-    fn from(hand_str: &str) -> Self {
-        let cards = hand_str.chars().map({|c| 
+// errors are reported on the first line, as a hand is parsed on its own.
+impl TryFrom<&str> for Hand {
+    type Error = ParseError;
+
+    fn try_from(hand_str: &str) -> Result<Self, Self::Error> {
+        let line = Line::new(DAY, 0, hand_str);
+        let cards = hand_str.char_indices().map({|(idx, c)|
             match c {
-                'A' => Ace,
-                'K' => King,
-                'Q' => Queen,
-                'J' => Jack,
-                'T' => Number(10),
-                _ => Number(c.to_digit(10).expect("Number to be parseable")),
+                'A' => Ok(Ace),
+                'K' => Ok(King),
+                'Q' => Ok(Queen),
+                'J' => Ok(Jack),
+                'T' => Ok(Number(10)),
+                '2'..='9' => Ok(Number(c.to_digit(10).unwrap_or_default())),
+                _ => Err(line.error(&hand_str[idx..idx + c.len_utf8()], "a card")),
             }
-        });
-        let occurrences = cards.clone().fold(HashMap::new(), |mut acc, c| {
+        }).collect::<Result<Vec<Card>, ParseError>>()?;
+        if cards.len() != 5 {
+            return Err(line.error(hand_str, "five cards"));
+        }
+        let occurrences = cards.iter().fold(HashMap::new(), |mut acc, c| {
             *acc.entry(c).or_insert(0) += 1;
             acc
         });
@@ -94,7 +107,7 @@ impl From<&str> for Hand {
         } else {
             HighCard
         };
-        Hand { cards: cards.collect(), hand_type }
+        Ok(Hand { cards, hand_type })
     }
 }
 
@@ -132,7 +145,7 @@ mod tests {
             cards: vec![Number(3), Number(2), Number(10), Number(3), King],
             hand_type: OnePair,
         };
-        assert_eq!(Hand::from(str), expected);
+        assert_eq!(Hand::try_from(str), Ok(expected));
     }
 
     #[test]
@@ -142,7 +155,7 @@ mod tests {
             cards: vec![Queen, Queen, Queen, Jack, Ace],
             hand_type: ThreeOfAKind,
         };
-        assert_eq!(Hand::from(str), expected);
+        assert_eq!(Hand::try_from(str), Ok(expected));
     }
 
     #[test]
@@ -159,4 +172,17 @@ mod tests {
         assert!(first_hand > second_hand);
         
     }
+
+    #[test]
+    fn test_hand_with_unknown_card() {
+        let expected = ParseError::Syntax {
+            day: 7,
+            line: 1,
+            column: 3,
+            text: String::from("1"),
+            expected: "a card",
+        };
+        assert_eq!(Hand::try_from("QQ1JA"), Err(expected));
+        assert_eq!(Hand::try_from("QQJA").unwrap_err().to_string(), "day 7, line 1, column 1: expected five cards, found \"QQJA\"");
+    }
 }
@@ -1 +1,3 @@
-pub mod p1;
\ No newline at end of file
+// unfinished: nothing is wired to a solver yet.
+#[allow(dead_code)]
+pub mod p1;
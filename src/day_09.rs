@@ -1,2 +1,44 @@
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::solver::Solver;
+
 pub mod p1;
-pub mod p2;
\ No newline at end of file
+pub mod p2;
+
+pub const DAY: u8 = 9;
+
+pub struct Day09;
+
+impl Solver for Day09 {
+    type Input = Vec<Vec<i32>>;
+    type Output = i32;
+
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead {
+        p1::parse_input(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output {
+        p1::sum_predictions(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(p2::sum_predictions(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{example, example_tests};
+
+    use super::*;
+
+    example_tests! {
+        solver: Day09,
+        report => {
+            input: example(include_str!("day_09/p1.rs"), "For example:"),
+            part1: 114,
+            part2: 2,
+        },
+    }
+}
@@ -108,8 +108,13 @@ Analyze your OASIS report and extrapolate the next value for each history. What
 is the sum of these extrapolated values?
 */
 
-use regex::Regex;
-use std::io::{BufRead, Write};
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::parse::Line;
+
+use super::DAY;
 
 fn compute_differences(history: Vec<i32>) -> Vec<Vec<i32>> {
     let mut differences = vec![history];
@@ -132,7 +137,7 @@ fn compute_differences_aux(differences: &mut Vec<Vec<i32>>, diff_idx: usize) {
     compute_differences_aux(differences, diff_idx + 1);
 }
 
-fn get_prediction(differences: &mut Vec<Vec<i32>>) -> i32 {
+fn get_prediction(differences: &mut [Vec<i32>]) -> i32 {
     // fill in the last step with a zero to start prediction.
     let mut value_below = 0;
     let last_step = differences.last_mut().expect("Differences to not be empty");
@@ -150,44 +155,37 @@ fn get_prediction(differences: &mut Vec<Vec<i32>>) -> i32 {
     prediction
 }
 
-fn parse_input<R>(reader: R) -> Vec<Vec<i32>>
+pub fn parse_input<R>(reader: R) -> Result<Vec<Vec<i32>>, ParseError>
 where
     R: BufRead,
 {
-    let regex = Regex::new(r"[-+]?\d+").unwrap();
     let mut histories = Vec::new();
-    for line in reader.lines() {
-        let line = line.expect("Input line to exist");
-        histories.push(
-            regex
-                .find_iter(&line)
-                .map(|n| n.as_str().parse::<i32>().expect("Number to be parseable"))
-                .collect(),
-        );
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| ParseError::io(DAY, idx, e))?;
+        let line = Line::new(DAY, idx, &line);
+        histories.push(line.signed(line.text())?);
     }
+    Ok(histories)
+}
+
+pub fn sum_predictions(histories: &[Vec<i32>]) -> i32 {
     histories
+        .iter()
+        .map(|h| {
+            let mut diffs = compute_differences(h.clone());
+            get_prediction(&mut diffs)
+        })
+        .sum()
 }
 
-pub fn solve<R, W>(reader: R, mut writer: W)
+pub fn solve<R>(reader: R) -> Result<Answer, ParseError>
 where
     R: BufRead,
-    W: Write,
 {
-    let histories = parse_input(reader);
-    let solution: i32 = histories
-        .into_iter()
-        .map(|h| {
-            let mut diffs = compute_differences(h);
-            get_prediction(&mut diffs)
-        })
-        .sum();
-
-    write!(
-        &mut writer,
-        "The sum of all extrapolated values is: {}",
-        solution
-    )
-    .unwrap();
+    let histories = parse_input(reader)?;
+    let solution = sum_predictions(&histories);
+
+    Ok(solution.into())
 }
 
 #[cfg(test)]
@@ -211,4 +209,18 @@ mod tests {
         ];
         assert_eq!(get_prediction(&mut differences), 18);
     }
+
+    #[test]
+    fn test_parse_input_with_overflowing_number() {
+        let input = b"0 3 6\n\
+                     1 99999999999 6";
+        let expected = ParseError::Syntax {
+            day: 9,
+            line: 2,
+            column: 3,
+            text: String::from("99999999999"),
+            expected: "a number",
+        };
+        assert_eq!(parse_input(&input[..]), Err(expected));
+    }
 }
@@ -1 +1,3 @@
+// unfinished: nothing is wired to a solver yet.
+#[allow(unused)]
 pub mod p1;
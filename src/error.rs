@@ -0,0 +1,105 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Malformed puzzle input. Lines and columns are 1-based so they can be
+/// matched against an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Io {
+        day: u8,
+        line: usize,
+        message: String,
+    },
+    Syntax {
+        day: u8,
+        line: usize,
+        column: usize,
+        text: String,
+        expected: &'static str,
+    },
+}
+
+impl ParseError {
+    pub fn io(day: u8, line_idx: usize, error: io::Error) -> ParseError {
+        ParseError::Io {
+            day,
+            line: line_idx + 1,
+            message: error.to_string(),
+        }
+    }
+
+    /// Builds a syntax error for `fragment`, which must be a subslice of
+    /// `line` so its column can be recovered.
+    pub fn syntax(
+        day: u8,
+        line_idx: usize,
+        line: &str,
+        fragment: &str,
+        expected: &'static str,
+    ) -> ParseError {
+        let offset = (fragment.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        ParseError::Syntax {
+            day,
+            line: line_idx + 1,
+            column: offset.min(line.len()) + 1,
+            text: fragment.to_string(),
+            expected,
+        }
+    }
+
+    pub fn day(&self) -> u8 {
+        match self {
+            ParseError::Io { day, .. } | ParseError::Syntax { day, .. } => *day,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            ParseError::Io { line, .. } | ParseError::Syntax { line, .. } => *line,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io { day, line, message } => {
+                write!(f, "day {}, line {}: failed to read input: {}", day, line, message)
+            }
+            ParseError::Syntax { day, line, column, text, expected } => write!(
+                f,
+                "day {}, line {}, column {}: expected {}, found {:?}",
+                day, line, column, expected, text
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syntax_column() {
+        let line = "Game 1: 3 purple";
+        let error = ParseError::syntax(2, 0, line, &line[10..], "a cube color");
+
+        assert_eq!(
+            error,
+            ParseError::Syntax {
+                day: 2,
+                line: 1,
+                column: 11,
+                text: String::from("purple"),
+                expected: "a cube color",
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 1, column 11: expected a cube color, found \"purple\""
+        );
+    }
+}
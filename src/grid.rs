@@ -0,0 +1,271 @@
+use std::fmt;
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+/// (row, column), i.e. (N-S, W-E), with (0, 0) in the top left corner.
+pub type Position = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1),
+    (0, -1),           (0, 1),
+    (1, -1),  (1, 0),  (1, 1),
+];
+
+/// Rectangular grid stored row by row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> where T: Clone {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Builds a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+        let height = rows.len();
+        Some(Grid { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    /// Parses one row per line, mapping each character to a cell. `map`
+    /// returns `None` for characters that are not valid cells.
+    pub fn parse<R, F>(day: u8, reader: R, mut map: F) -> Result<Grid<T>, ParseError>
+    where
+        R: BufRead,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| ParseError::io(day, idx, e))?;
+            let row_start = cells.len();
+            for (col, c) in line.char_indices() {
+                let cell = map(c).ok_or_else(|| {
+                    ParseError::syntax(day, idx, &line, &line[col..col + c.len_utf8()], "a grid cell")
+                })?;
+                cells.push(cell);
+            }
+            let row_width = cells.len() - row_start;
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(ParseError::syntax(day, idx, &line, &line, "a row as wide as the first"));
+            }
+            height += 1;
+        }
+        Ok(Grid { width: width.unwrap_or(0), height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    fn offset(&self, (row, col): Position, (d_row, d_col): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(d_row)?, col.checked_add_signed(d_col)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Orthogonal neighbours (N, W, E, S) that lie within the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_4.iter().filter_map(move |&offset| self.offset(position, offset))
+    }
+
+    /// Orthogonal and diagonal neighbours that lie within the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_8.iter().filter_map(move |&offset| self.offset(position, offset))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero size; grids without columns have no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let height = if col < self.width { self.height } else { 0 };
+        self.cells.iter().skip(col).step_by(self.width.max(1)).take(height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Inserts a row before `index`, shifting the rows below it down.
+    ///
+    /// Panics if `index > height` or the row does not match the grid width.
+    pub fn insert_row(&mut self, index: usize, row: Vec<T>) {
+        assert!(index <= self.height, "Row index {} out of bounds", index);
+        if self.height == 0 && self.width == 0 {
+            self.width = row.len();
+        }
+        assert_eq!(row.len(), self.width, "Row width to match the grid");
+        let at = index * self.width;
+        self.cells.splice(at..at, row);
+        self.height += 1;
+    }
+
+    /// Inserts a column before `index`, shifting the columns to its right.
+    ///
+    /// Panics if `index > width` or the column does not match the grid height.
+    pub fn insert_column(&mut self, index: usize, column: Vec<T>) {
+        assert!(index <= self.width, "Column index {} out of bounds", index);
+        if self.height == 0 && self.width == 0 {
+            self.height = column.len();
+        }
+        assert_eq!(column.len(), self.height, "Column height to match the grid");
+        let mut cells = Vec::with_capacity(self.cells.len() + self.height);
+        let mut old = self.cells.drain(..);
+        for cell in column {
+            cells.extend(old.by_ref().take(index));
+            cells.push(cell);
+            cells.extend(old.by_ref().take(self.width - index));
+        }
+        drop(old);
+        self.cells = cells;
+        self.width += 1;
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).expect("Position to be within the grid")
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position).expect("Position to be within the grid")
+    }
+}
+
+impl<T> fmt::Display for Grid<T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse(0, &b"abc\ndef"[..], Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+    }
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        let error = Grid::parse(3, &b"...\n..\n"[..], Some).unwrap_err();
+        assert_eq!(error.line(), 2);
+    }
+
+    #[test]
+    fn test_parse_unmapped_char() {
+        let map = |c: char| c.to_digit(10);
+        let expected = ParseError::Syntax {
+            day: 11,
+            line: 1,
+            column: 2,
+            text: String::from("x"),
+            expected: "a grid cell",
+        };
+        assert_eq!(Grid::parse(11, &b"1x3"[..], map), Err(expected));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, '.');
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours4((2, 2)).collect::<Vec<_>>(), vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = sample();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&['a', 'b', 'c'][..], &['d', 'e', 'f'][..]]);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), vec!["ad", "be", "cf"]);
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn test_insert_row_and_column() {
+        let mut grid = sample();
+        grid.insert_row(1, vec!['x', 'y', 'z']);
+        grid.insert_column(3, vec!['1', '2', '3']);
+        grid.insert_column(0, vec!['_', '_', '_']);
+        assert_eq!(grid.to_string(), "_abc1\n_xyz2\n_def3");
+        assert_eq!(grid.iter().nth(6), Some(((1, 1), &'x')));
+    }
+
+    #[test]
+    fn test_from_rows() {
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).map(|g| g[(1, 0)]), Some(3));
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+}
@@ -0,0 +1,153 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+/// Input directory used when neither `--input-dir` nor `ADVENT_INPUT_DIR` is
+/// set.
+pub const DEFAULT_DIR: &str = "inputs";
+
+pub const DIR_VAR: &str = "ADVENT_INPUT_DIR";
+pub const CACHE_VAR: &str = "ADVENT_CACHE_DIR";
+
+/// Locates puzzle inputs on disk.
+///
+/// For a given (day, part) the following files are tried, in order, first in
+/// the input directory and then in the cache directory (if any):
+///
+/// - `day_NN_pP.txt`, for puzzles whose parts use different inputs;
+/// - `day_NN.txt`, the input shared by both parts of the day.
+///
+/// Nothing is ever downloaded: the cache is only filled by inputs that were
+/// read from stdin, so later runs can find them offline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inputs {
+    dir: PathBuf,
+    cache_dir: Option<PathBuf>,
+}
+
+impl Inputs {
+    pub fn new<P>(dir: P) -> Inputs where P: Into<PathBuf> {
+        Inputs { dir: dir.into(), cache_dir: None }
+    }
+
+    pub fn with_dir<P>(mut self, dir: P) -> Inputs where P: Into<PathBuf> {
+        self.dir = dir.into();
+        self
+    }
+
+    pub fn with_cache<P>(mut self, cache_dir: P) -> Inputs where P: Into<PathBuf> {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Configuration taken from `ADVENT_INPUT_DIR` and `ADVENT_CACHE_DIR`.
+    pub fn from_env() -> Inputs {
+        let inputs = Inputs::new(env::var_os(DIR_VAR).unwrap_or_else(|| DEFAULT_DIR.into()));
+        match env::var_os(CACHE_VAR) {
+            Some(cache_dir) => inputs.with_cache(cache_dir),
+            None => inputs,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    pub fn file_names(day: u8, part: u8) -> [String; 2] {
+        [format!("day_{:02}_p{}.txt", day, part), format!("day_{:02}.txt", day)]
+    }
+
+    /// Every path that is tried for (day, part), in lookup order.
+    pub fn candidates(&self, day: u8, part: u8) -> Vec<PathBuf> {
+        let names = Inputs::file_names(day, part);
+        let dirs = std::iter::once(&self.dir).chain(self.cache_dir.iter());
+        dirs.flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+            .collect()
+    }
+
+    pub fn find(&self, day: u8, part: u8) -> Option<PathBuf> {
+        self.candidates(day, part).into_iter().find(|path| path.is_file())
+    }
+
+    /// Opens the input for (day, part), falling back to stdin when no file
+    /// exists. Inputs read from stdin are copied to the cache when one is
+    /// configured.
+    pub fn open(&self, day: u8, part: u8) -> io::Result<Box<dyn BufRead>> {
+        match self.find(day, part) {
+            Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            None => self.read_to_cache(day, io::stdin().lock()),
+        }
+    }
+
+    fn read_to_cache<R>(&self, day: u8, mut reader: R) -> io::Result<Box<dyn BufRead>> where R: Read + 'static {
+        let Some(cache_dir) = &self.cache_dir else {
+            return Ok(Box::new(BufReader::new(reader)));
+        };
+        let mut contents = Vec::new();
+        reader.read_to_end(&mut contents)?;
+        fs::create_dir_all(cache_dir)?;
+        fs::write(cache_dir.join(format!("day_{:02}.txt", day)), &contents)?;
+        Ok(Box::new(Cursor::new(contents)))
+    }
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Inputs::new(DEFAULT_DIR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("advent_2023_inputs_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_candidates_order() {
+        let inputs = Inputs::new("inputs").with_cache("cache");
+        let expected: Vec<PathBuf> = vec![
+            "inputs/day_09_p2.txt".into(),
+            "inputs/day_09.txt".into(),
+            "cache/day_09_p2.txt".into(),
+            "cache/day_09.txt".into(),
+        ];
+        assert_eq!(inputs.candidates(9, 2), expected);
+    }
+
+    #[test]
+    fn test_find_prefers_part_specific_input() {
+        let dir = scratch_dir("find");
+        fs::write(dir.join("day_01.txt"), "shared").unwrap();
+        fs::write(dir.join("day_01_p2.txt"), "part 2").unwrap();
+        let inputs = Inputs::new(&dir);
+
+        assert_eq!(inputs.find(1, 1), Some(dir.join("day_01.txt")));
+        assert_eq!(inputs.find(1, 2), Some(dir.join("day_01_p2.txt")));
+        assert_eq!(inputs.find(2, 1), None);
+    }
+
+    #[test]
+    fn test_read_to_cache() {
+        let dir = scratch_dir("cache");
+        let cache_dir = dir.join("cache");
+        let inputs = Inputs::new(dir.join("missing")).with_cache(&cache_dir);
+
+        let mut reader = inputs.read_to_cache(4, &b"Card 1: 1 | 1"[..]).unwrap();
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).unwrap();
+
+        assert_eq!(contents, "Card 1: 1 | 1");
+        assert_eq!(inputs.find(4, 1), Some(cache_dir.join("day_04.txt")));
+    }
+}
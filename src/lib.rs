@@ -1,3 +1,9 @@
+use std::collections::BTreeMap;
+
+use solver::{part1, part2, Solution};
+
+pub mod answer;
+pub mod bench;
 pub mod day_01;
 pub mod day_02;
 pub mod day_03;
@@ -10,3 +16,46 @@ pub mod day_09;
 pub mod day_10;
 pub mod day_11;
 pub mod day_12;
+pub mod error;
+pub mod grid;
+pub mod inputs;
+pub mod output;
+pub mod parse;
+pub mod solver;
+#[cfg(test)]
+mod test_support;
+pub mod verify;
+
+/// Every available solution, keyed by (day, part).
+pub fn registry() -> BTreeMap<(u8, u8), Solution> {
+    BTreeMap::from([
+        ((1, 1), part1::<day_01::Day01> as Solution),
+        ((1, 2), part2::<day_01::Day01>),
+        ((2, 1), part1::<day_02::Day02>),
+        ((2, 2), part2::<day_02::Day02>),
+        ((3, 1), part1::<day_03::Day03>),
+        ((3, 2), part2::<day_03::Day03>),
+        ((4, 1), part1::<day_04::Day04>),
+        ((4, 2), part2::<day_04::Day04>),
+        ((5, 1), part1::<day_05::Day05>),
+        ((6, 1), part1::<day_06::Day06>),
+        ((9, 1), part1::<day_09::Day09>),
+        ((9, 2), part2::<day_09::Day09>),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use answer::Answer;
+
+    #[test]
+    fn test_registry_runs_solution() {
+        let input = b"0 3 6 9 12 15\n\
+                     1 3 6 10 15 21\n\
+                     10 13 16 21 30 45";
+        let solution = registry()[&(9, 2)];
+
+        assert_eq!(solution(&mut &input[..]).unwrap().answer, Answer::Signed(2));
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::answer::Answer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Plain,
+    Json,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("Unsupported format: {} (expected plain, json or tsv)", s)),
+        }
+    }
+}
+
+/// Outcome of running a single (day, part).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+impl Format {
+    /// Line printed before the records, if the format has one.
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            Format::Tsv => Some("day\tpart\tanswer\ttime_ms"),
+            Format::Plain | Format::Json => None,
+        }
+    }
+
+    /// Renders a record as a single line. JSON output is one object per line.
+    pub fn render(&self, record: &Record) -> String {
+        let millis = record.elapsed.as_secs_f64() * 1000.0;
+        match self {
+            Format::Plain => format!(
+                "day {:02} part {}: {} ({:.3} ms)",
+                record.day, record.part, record.answer, millis
+            ),
+            Format::Json => format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"time_ms\":{:.3}}}",
+                record.day,
+                record.part,
+                json_answer(&record.answer),
+                millis
+            ),
+            Format::Tsv => format!(
+                "{}\t{}\t{}\t{:.3}",
+                record.day,
+                record.part,
+                tsv_field(&record.answer.to_string()),
+                millis
+            ),
+        }
+    }
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Unsigned(n) => n.to_string(),
+        Answer::Signed(n) => n.to_string(),
+        Answer::Text(s) => {
+            let mut escaped = String::from("\"");
+            for c in s.chars() {
+                match c {
+                    '"' => escaped.push_str("\\\""),
+                    '\\' => escaped.push_str("\\\\"),
+                    '\n' => escaped.push_str("\\n"),
+                    '\t' => escaped.push_str("\\t"),
+                    c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+                    c => escaped.push(c),
+                }
+            }
+            escaped.push('"');
+            escaped
+        }
+    }
+}
+
+// tabs and newlines would break the row layout, so they are flattened.
+fn tsv_field(field: &str) -> String {
+    field.replace(['\t', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Answer) -> Record {
+        Record {
+            day: 9,
+            part: 2,
+            answer,
+            elapsed: Duration::from_micros(1500),
+        }
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let actual = Format::Plain.render(&record(Answer::Signed(-3)));
+        assert_eq!(actual, "day 09 part 2: -3 (1.500 ms)");
+    }
+
+    #[test]
+    fn test_render_json() {
+        let actual = Format::Json.render(&record(Answer::Unsigned(114)));
+        assert_eq!(actual, r#"{"day":9,"part":2,"answer":114,"time_ms":1.500}"#);
+    }
+
+    #[test]
+    fn test_render_json_escapes_text() {
+        let actual = Format::Json.render(&record(Answer::Text(String::from("a \"b\"\n"))));
+        assert_eq!(actual, r#"{"day":9,"part":2,"answer":"a \"b\"\n","time_ms":1.500}"#);
+    }
+
+    #[test]
+    fn test_render_tsv() {
+        let actual = Format::Tsv.render(&record(Answer::Text(String::from("a\tb"))));
+        assert_eq!(actual, "9\t2\ta b\t1.500");
+    }
+}
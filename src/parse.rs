@@ -0,0 +1,204 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+/// A line of puzzle input, remembering where it came from so the helpers
+/// below can report errors at the right position. Every `fragment` passed to
+/// its methods must be a subslice of the line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    day: u8,
+    idx: usize,
+    text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(day: u8, idx: usize, text: &'a str) -> Line<'a> {
+        Line { day, idx, text }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// 0-based index of the line in the input.
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    pub fn error(&self, fragment: &str, expected: &'static str) -> ParseError {
+        ParseError::syntax(self.day, self.idx, self.text, fragment, expected)
+    }
+
+    pub fn split_once(
+        &self,
+        fragment: &'a str,
+        separator: char,
+        expected: &'static str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        fragment.split_once(separator).ok_or_else(|| self.error(fragment, expected))
+    }
+
+    pub fn number<T>(&self, token: &'a str, expected: &'static str) -> Result<T, ParseError> where T: FromStr {
+        token.trim().parse().map_err(|_| self.error(token, expected))
+    }
+
+    /// Parses a `<label> <value>: <rest>` header such as "Game 12: ...",
+    /// returning the value and the text after the colon.
+    pub fn header<T>(&self, label: &str) -> Result<(T, &'a str), ParseError> where T: FromStr {
+        let (header, rest) = self.split_once(self.text, ':', "a header followed by ':'")?;
+        let value = header
+            .trim_start()
+            .strip_prefix(label)
+            .ok_or_else(|| self.error(header, "a header label"))?;
+        Ok((self.number(value, "a header value")?, rest))
+    }
+
+    /// Every unsigned integer in `fragment`, ignoring the text around them.
+    pub fn unsigned<T>(&self, fragment: &'a str) -> Result<Vec<T>, ParseError> where T: FromStr {
+        unsigned_tokens(fragment)
+            .map(|(_, token)| self.number(token, "a number"))
+            .collect()
+    }
+
+    /// Every integer in `fragment`, including a leading `-` or `+` sign.
+    pub fn signed<T>(&self, fragment: &'a str) -> Result<Vec<T>, ParseError> where T: FromStr {
+        signed_tokens(fragment)
+            .map(|(_, token)| self.number(token, "a number"))
+            .collect()
+    }
+}
+
+/// Runs of ASCII digits in `text`, with their byte offsets.
+pub fn unsigned_tokens(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    tokens(text, false)
+}
+
+/// Runs of ASCII digits in `text`, including a sign immediately before them,
+/// with their byte offsets.
+pub fn signed_tokens(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    tokens(text, true)
+}
+
+fn tokens(text: &str, signed: bool) -> impl Iterator<Item = (usize, &str)> {
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let digits_start = pos + bytes[pos..].iter().position(u8::is_ascii_digit)?;
+        let digits_end = digits_start
+            + bytes[digits_start..].iter().position(|b| !b.is_ascii_digit()).unwrap_or(bytes.len() - digits_start);
+        let start = if signed && digits_start > pos && matches!(bytes[digits_start - 1], b'-' | b'+') {
+            digits_start - 1
+        } else {
+            digits_start
+        };
+        pos = digits_end;
+        Some((start, &text[start..digits_end]))
+    })
+}
+
+/// Reads the whole input, for puzzles that are not line oriented.
+pub fn read_all<R>(day: u8, mut reader: R) -> Result<String, ParseError> where R: BufRead {
+    let mut text = String::new();
+    reader.read_to_string(&mut text).map_err(|e| ParseError::io(day, 0, e))?;
+    Ok(text)
+}
+
+/// Lines of `text` together with their positions.
+pub fn lines(day: u8, text: &str) -> impl Iterator<Item = Line<'_>> {
+    text.lines().enumerate().map(move |(idx, l)| Line::new(day, idx, l))
+}
+
+/// Groups of consecutive non-blank lines, as separated by blank lines.
+pub fn blocks(day: u8, text: &str) -> impl Iterator<Item = Vec<Line<'_>>> {
+    let mut lines = lines(day, text).peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|l| l.text().trim().is_empty()).is_some() {}
+        let mut block = Vec::new();
+        while let Some(line) = lines.next_if(|l| !l.text().trim().is_empty()) {
+            block.push(line);
+        }
+        (!block.is_empty()).then_some(block)
+    })
+}
+
+/// A block starting with a `label:` line, like "seeds: 79 14" or
+/// "seed-to-soil map:" followed by its entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub label: &'a str,
+    pub header: Line<'a>,
+    /// Text after the colon on the header line.
+    pub inline: &'a str,
+    /// Lines after the header line.
+    pub lines: Vec<Line<'a>>,
+}
+
+impl<'a> Section<'a> {
+    pub fn expect_label(&self, label: &str, expected: &'static str) -> Result<(), ParseError> {
+        if self.label == label {
+            Ok(())
+        } else {
+            Err(self.header.error(self.label, expected))
+        }
+    }
+}
+
+/// Splits `text` into blank-line separated sections.
+pub fn sections(day: u8, text: &str) -> Result<Vec<Section<'_>>, ParseError> {
+    blocks(day, text)
+        .map(|block| {
+            let header = block[0];
+            let (label, inline) = header.split_once(header.text(), ':', "a section label followed by ':'")?;
+            Ok(Section { label: label.trim(), header, inline, lines: block[1..].to_vec() })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let text = "a12 -3+4 5-";
+        assert_eq!(unsigned_tokens(text).collect::<Vec<_>>(), vec![(1, "12"), (5, "3"), (7, "4"), (9, "5")]);
+        assert_eq!(signed_tokens(text).collect::<Vec<_>>(), vec![(1, "12"), (4, "-3"), (6, "+4"), (9, "5")]);
+        assert_eq!(unsigned_tokens("none").count(), 0);
+    }
+
+    #[test]
+    fn test_header() {
+        let line = Line::new(2, 0, "Game 12: 3 blue");
+        assert_eq!(line.header::<u32>("Game"), Ok((12, " 3 blue")));
+        assert_eq!(line.header::<u32>("Card").unwrap_err().to_string(),
+                   "day 2, line 1, column 1: expected a header label, found \"Game 12\"");
+    }
+
+    #[test]
+    fn test_numbers_report_position() {
+        let line = Line::new(9, 4, "1 -2 99999999999");
+        assert_eq!(line.signed::<i64>(line.text()), Ok(vec![1, -2, 99999999999]));
+        let expected = ParseError::Syntax {
+            day: 9,
+            line: 5,
+            column: 6,
+            text: String::from("99999999999"),
+            expected: "a number",
+        };
+        assert_eq!(line.signed::<i32>(line.text()), Err(expected));
+    }
+
+    #[test]
+    fn test_sections() {
+        let text = "seeds: 79 14\n\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        let sections = sections(5, text).unwrap();
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!((sections[0].label, sections[0].inline), ("seeds", " 79 14"));
+        assert_eq!(sections[1].label, "seed-to-soil map");
+        assert_eq!(sections[1].lines.iter().map(|l| l.idx()).collect::<Vec<_>>(), vec![4, 5]);
+        assert!(sections[1].expect_label("soil-to-fertilizer map", "a map").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+/// Common shape of every puzzle solution: the input is parsed once and then
+/// handed to each part.
+pub trait Solver {
+    type Input;
+    type Output: Into<Answer>;
+
+    fn parse<R>(reader: R) -> Result<Self::Input, ParseError> where R: BufRead;
+
+    fn part1(input: &Self::Input) -> Self::Output;
+
+    // days whose second part is not solved yet keep the default.
+    fn part2(_input: &Self::Input) -> Option<Self::Output> {
+        None
+    }
+}
+
+/// Answer of a single run, along with the time spent in each phase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl Run {
+    pub fn elapsed(&self) -> Duration {
+        self.parse_time + self.solve_time
+    }
+}
+
+/// Type-erased entry point stored in the registry, so solvers with different
+/// input and output types can live side by side.
+pub type Solution = fn(&mut dyn BufRead) -> Result<Run, ParseError>;
+
+fn timed<S, F>(reader: &mut dyn BufRead, solve: F) -> Result<Run, ParseError>
+where
+    S: Solver,
+    F: Fn(&S::Input) -> S::Output,
+{
+    let start = Instant::now();
+    let input = S::parse(reader)?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = solve(&input).into();
+    let solve_time = start.elapsed();
+
+    Ok(Run { answer, parse_time, solve_time })
+}
+
+pub fn part1<S>(reader: &mut dyn BufRead) -> Result<Run, ParseError> where S: Solver {
+    timed::<S, _>(reader, S::part1)
+}
+
+pub fn part2<S>(reader: &mut dyn BufRead) -> Result<Run, ParseError> where S: Solver {
+    timed::<S, _>(reader, |input| {
+        S::part2(input).expect("Part 2 to be implemented for registered solvers")
+    })
+}
@@ -0,0 +1,126 @@
+/// Extracts a worked example from the puzzle text embedded in a day's source:
+/// the first block of consecutive non-blank lines after the line containing
+/// `marker`.
+pub fn example(source: &str, marker: &str) -> String {
+    let mut lines = source
+        .lines()
+        .skip_while(|l| !l.contains(marker))
+        .skip(1)
+        .skip_while(|l| l.trim().is_empty());
+    let mut example: Vec<&str> = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim().is_empty() || line.trim_start().starts_with("*/") {
+            break;
+        }
+        example.push(line);
+    }
+    assert!(!example.is_empty(), "No example found after {:?}", marker);
+    example.join("\n")
+}
+
+/// Like `example`, for examples that span blank lines or run straight into
+/// the prose: every line after `marker` up to the line containing `end`,
+/// without the blank lines around them.
+pub fn example_until(source: &str, marker: &str, end: &str) -> String {
+    let example: Vec<&str> = source
+        .lines()
+        .skip_while(|l| !l.contains(marker))
+        .skip(1)
+        .take_while(|l| !l.contains(end))
+        .collect();
+    let example = example.join("\n");
+    assert!(!example.trim().is_empty(), "No example found between {:?} and {:?}", marker, end);
+    String::from(example.trim_matches('\n'))
+}
+
+/// Generates parse, part 1 and part 2 tests for a `Solver` from its worked
+/// examples. Each example gets its own module, and each part is optional
+/// since examples often only cover one of them:
+///
+/// ```ignore
+/// example_tests! {
+///     solver: Day09,
+///     report => {
+///         input: EXAMPLE,
+///         part1: 114,
+///         part2: 2,
+///     },
+/// }
+/// ```
+macro_rules! example_tests {
+    (
+        solver: $solver:ty,
+        $(
+            $name:ident => {
+                input: $input:expr
+                $(, part1: $part1:expr)?
+                $(, part2: $part2:expr)?
+                $(,)?
+            }
+        ),+ $(,)?
+    ) => {
+        $(
+            mod $name {
+                #[allow(unused_imports)]
+                use super::*;
+                use $crate::solver::Solver;
+
+                fn parse() -> <$solver as Solver>::Input {
+                    let input: String = ($input).into();
+                    <$solver as Solver>::parse(input.as_bytes()).expect("Example to parse")
+                }
+
+                #[test]
+                fn test_parse() {
+                    parse();
+                }
+
+                $(
+                    #[test]
+                    fn test_part1() {
+                        assert_eq!(<$solver as Solver>::part1(&parse()), $part1);
+                    }
+                )?
+
+                $(
+                    #[test]
+                    fn test_part2() {
+                        assert_eq!(<$solver as Solver>::part2(&parse()), Some($part2));
+                    }
+                )?
+            }
+        )+
+    };
+}
+
+pub(crate) use example_tests;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let source = "/*\n\
+                      For example:\n\
+                      \n\
+                      1 2\n\
+                      3 4\n\
+                      \n\
+                      In this example\n\
+                      */";
+        assert_eq!(example(source, "For example:"), "1 2\n3 4");
+        assert_eq!(example_until(source, "For example:", "In this"), "1 2\n3 4");
+    }
+
+    #[test]
+    fn test_example_until_spans_blank_lines() {
+        let source = "For example:\n\
+                      seeds: 1\n\
+                      \n\
+                      map:\n\
+                      2 3\n\
+                      The map says";
+        assert_eq!(example_until(source, "For example:", "The map"), "seeds: 1\n\nmap:\n2 3");
+    }
+}
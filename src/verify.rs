@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::answer::Answer;
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Known-good answers, read from a small TOML subset:
+///
+/// ```toml
+/// [day_09]
+/// part1 = 114
+/// part2 = "2"
+/// ```
+///
+/// Values may be integers or strings; they are compared against the rendered
+/// answer, so `2` and `"2"` are equivalent.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AnswersError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl Answers {
+    pub fn parse(contents: &str) -> Result<Answers, AnswersError> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+        for (idx, line) in contents.lines().enumerate() {
+            let error = |message: &str| AnswersError { line: idx + 1, message: message.to_string() };
+            let line = line.split_once('#').map_or(line, |(l, _)| l).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let parsed = table
+                    .trim()
+                    .strip_prefix("day_")
+                    .and_then(|d| d.parse::<u8>().ok())
+                    .ok_or_else(|| error("expected a [day_NN] table"))?;
+                day = Some(parsed);
+                continue;
+            }
+            let day = day.ok_or_else(|| error("answer outside of a [day_NN] table"))?;
+            let (key, value) = line.split_once('=').ok_or_else(|| error("expected partN = value"))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse::<u8>().ok())
+                .ok_or_else(|| error("expected a partN key"))?;
+            let value = value.trim();
+            let value = match value.strip_prefix('"') {
+                Some(quoted) => quoted
+                    .strip_suffix('"')
+                    .ok_or_else(|| error("unterminated string"))?
+                    .to_string(),
+                None if value.parse::<i64>().is_ok() || value.parse::<u64>().is_ok() => value.to_string(),
+                None => return Err(error("expected an integer or a string")),
+            };
+            answers.insert((day, part), value);
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: u8, actual: &Answer) -> Outcome {
+        match self.get(day, part) {
+            None => Outcome::MissingAnswer,
+            Some(expected) if expected == actual.to_string() => Outcome::Pass,
+            Some(expected) => Outcome::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+/// Result of verifying a single (day, part).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    MissingAnswer,
+    MissingInput,
+    Error(String),
+}
+
+impl Outcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Outcome::MissingAnswer => write!(f, "missing (no recorded answer)"),
+            Outcome::MissingInput => write!(f, "missing (no input)"),
+            Outcome::Error(message) => write!(f, "ERROR ({})", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let contents = "# recorded answers\n\
+                        [day_09]\n\
+                        part1 = 114\n\
+                        part2 = \"-3\" # backwards\n";
+        let answers = Answers::parse(contents).unwrap();
+
+        assert_eq!(answers.get(9, 1), Some("114"));
+        assert_eq!(answers.get(9, 2), Some("-3"));
+        assert_eq!(answers.get(1, 1), None);
+    }
+
+    #[test]
+    fn test_parse_answer_outside_table() {
+        let expected = AnswersError {
+            line: 1,
+            message: String::from("answer outside of a [day_NN] table"),
+        };
+        assert_eq!(Answers::parse("part1 = 1"), Err(expected));
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("[day_01]\npart1 = 142").unwrap();
+
+        assert_eq!(answers.check(1, 1, &Answer::Unsigned(142)), Outcome::Pass);
+        assert_eq!(
+            answers.check(1, 1, &Answer::Unsigned(141)),
+            Outcome::Fail { expected: String::from("142"), actual: String::from("141") }
+        );
+        assert_eq!(answers.check(1, 2, &Answer::Unsigned(281)), Outcome::MissingAnswer);
+    }
+}